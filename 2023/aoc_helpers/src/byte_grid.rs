@@ -0,0 +1,149 @@
+use std::ops::Index;
+
+use crate::grid::Grid;
+use crate::parse::{GridParseError, GridParseErrorKind};
+
+/// A read-only byte grid that borrows the puzzle input instead of copying it.
+///
+/// Rows are indexed straight into the input, line terminators included, so
+/// construction only has to validate that every line has the same length.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteGrid<'a> {
+    bytes: &'a [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    pub fn new(input: &'a str) -> Result<Self, GridParseError> {
+        let bytes = input.as_bytes();
+        let width = input.lines().next().map_or(0, str::len);
+        let stride = bytes
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(width, |i| i + 1);
+
+        let mut height = 0;
+        for (r, line) in input.lines().enumerate() {
+            let offset = line.as_ptr() as usize - bytes.as_ptr() as usize;
+            if line.len() != width || offset != r * stride {
+                return Err(GridParseError {
+                    line: r + 1,
+                    column: width.min(line.len()) + 1,
+                    kind: GridParseErrorKind::Ragged {
+                        expected: width,
+                        found: line.len(),
+                    },
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            bytes,
+            width,
+            height,
+            stride,
+        })
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// (height, width)
+    #[inline]
+    pub fn dims(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    #[inline]
+    pub fn contains(&self, (r, c): (usize, usize)) -> bool {
+        r < self.height && c < self.width
+    }
+
+    #[inline]
+    pub fn get(&self, pos: (usize, usize)) -> Option<u8> {
+        self.contains(pos).then(|| self[pos])
+    }
+
+    /// Returns the cell at `pos` moved by `(dr, dc)`.
+    #[inline]
+    pub fn get_offset(&self, (r, c): (usize, usize), (dr, dc): (isize, isize)) -> Option<u8> {
+        let (r, c) = (r as isize + dr, c as isize + dc);
+        if r < 0 || c < 0 {
+            return None;
+        }
+        self.get((r as usize, c as usize))
+    }
+
+    /// The bytes of row `r`, without the line terminator.
+    #[inline]
+    pub fn row(&self, r: usize) -> &'a [u8] {
+        let start = r * self.stride;
+        &self.bytes[start..start + self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.height).map(|r| self.row(r))
+    }
+
+    /// Iterates over every position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    /// Copies the cells into an owned, mutable [`Grid`].
+    pub fn to_grid(&self) -> Grid<u8> {
+        Grid::from_vec(self.width, self.rows().flatten().copied().collect())
+    }
+}
+
+impl Index<(usize, usize)> for ByteGrid<'_> {
+    type Output = u8;
+
+    #[inline]
+    fn index(&self, (r, c): (usize, usize)) -> &u8 {
+        assert!(self.contains((r, c)), "{:?} out of bounds", (r, c));
+        &self.bytes[r * self.stride + c]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrows_rows() {
+        let input = "#.#\n.S.\n#.#\n";
+        let grid = ByteGrid::new(input).unwrap();
+        assert_eq!(grid.dims(), (3, 3));
+        assert_eq!(grid[(1, 1)], b'S');
+        assert_eq!(grid.row(2), b"#.#");
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get_offset((1, 1), (-1, -1)), Some(b'#'));
+        assert_eq!(grid.to_grid(), Grid::<u8>::parse(input).unwrap());
+    }
+
+    #[test]
+    fn crlf() {
+        let grid = ByteGrid::new("ab\r\ncd").unwrap();
+        assert_eq!(grid.dims(), (2, 2));
+        assert_eq!(grid[(1, 0)], b'c');
+    }
+
+    #[test]
+    fn ragged() {
+        let err = ByteGrid::new("abc\nab\nabc").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(ByteGrid::new("ab\ncd\r\nef").is_err());
+    }
+}
@@ -1 +1,385 @@
+use std::fmt::Display;
+use std::str::FromStr;
 
+/// One of the four orthogonal directions.
+///
+/// Variants are ordered clockwise starting from `Up`, which is also the
+/// order of [`Direction::ALL`] and the value of [`Direction::to_bits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Direction {
+    Up = 0,
+    Right = 1,
+    Down = 2,
+    Left = 3,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// (row, column) offset of a single step.
+    #[inline]
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+
+    /// The arrow pointing this way, as used by `Display`.
+    #[inline]
+    pub const fn arrow(self) -> char {
+        match self {
+            Direction::Up => '↑',
+            Direction::Right => '→',
+            Direction::Down => '↓',
+            Direction::Left => '←',
+        }
+    }
+
+    #[inline]
+    pub const fn turn_right(self) -> Self {
+        Self::from_bits(self as u8 + 1)
+    }
+
+    #[inline]
+    pub const fn turn_left(self) -> Self {
+        Self::from_bits(self as u8 + 3)
+    }
+
+    #[inline]
+    pub const fn reverse(self) -> Self {
+        Self::from_bits(self as u8 + 2)
+    }
+
+    #[inline]
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    #[inline]
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// Packs the direction into the low 2 bits.
+    #[inline]
+    pub const fn to_bits(self) -> u8 {
+        self as u8
+    }
+
+    /// Unpacks a direction from the low 2 bits, ignoring the rest.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => Direction::Up,
+            1 => Direction::Right,
+            2 => Direction::Down,
+            _ => Direction::Left,
+        }
+    }
+
+    /// Returns the direction with the given (row, column) offset.
+    pub fn from_delta(delta: (isize, isize)) -> Option<Self> {
+        Self::iter().find(|d| d.delta() == delta)
+    }
+}
+
+impl From<Direction> for usize {
+    fn from(value: Direction) -> Self {
+        value as usize
+    }
+}
+
+impl From<Direction> for (isize, isize) {
+    fn from(value: Direction) -> Self {
+        value.delta()
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    /// Accepts arrows (`^>v<`), `UDLR` and compass points (`NESW`).
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            'v' | 'D' | 'S' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(c.to_string())),
+        }
+    }
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(b: u8) -> Result<Self, Self::Error> {
+        Direction::try_from(b as char)
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+/// One of the eight orthogonal and diagonal directions.
+///
+/// Variants are ordered clockwise starting from `N`, which is also the
+/// order of [`Direction8::ALL`] and the value of [`Direction8::to_bits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Direction8 {
+    N = 0,
+    NE = 1,
+    E = 2,
+    SE = 3,
+    S = 4,
+    SW = 5,
+    W = 6,
+    NW = 7,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// (row, column) offset of a single step.
+    #[inline]
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            Direction8::N => (-1, 0),
+            Direction8::NE => (-1, 1),
+            Direction8::E => (0, 1),
+            Direction8::SE => (1, 1),
+            Direction8::S => (1, 0),
+            Direction8::SW => (1, -1),
+            Direction8::W => (0, -1),
+            Direction8::NW => (-1, -1),
+        }
+    }
+
+    /// Rotates 45 degrees clockwise.
+    #[inline]
+    pub const fn turn_right(self) -> Self {
+        Self::from_bits(self as u8 + 1)
+    }
+
+    /// Rotates 45 degrees counter-clockwise.
+    #[inline]
+    pub const fn turn_left(self) -> Self {
+        Self::from_bits(self as u8 + 7)
+    }
+
+    #[inline]
+    pub const fn reverse(self) -> Self {
+        Self::from_bits(self as u8 + 4)
+    }
+
+    #[inline]
+    pub const fn is_diagonal(self) -> bool {
+        self as u8 & 1 == 1
+    }
+
+    /// Packs the direction into the low 3 bits.
+    #[inline]
+    pub const fn to_bits(self) -> u8 {
+        self as u8
+    }
+
+    /// Unpacks a direction from the low 3 bits, ignoring the rest.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0b111 {
+            0 => Direction8::N,
+            1 => Direction8::NE,
+            2 => Direction8::E,
+            3 => Direction8::SE,
+            4 => Direction8::S,
+            5 => Direction8::SW,
+            6 => Direction8::W,
+            _ => Direction8::NW,
+        }
+    }
+
+    /// Returns the direction with the given (row, column) offset.
+    pub fn from_delta(delta: (isize, isize)) -> Option<Self> {
+        Self::iter().find(|d| d.delta() == delta)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        Direction8::from_bits(value.to_bits() * 2)
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = Direction8;
+
+    fn try_from(value: Direction8) -> Result<Self, Self::Error> {
+        if value.is_diagonal() {
+            Err(value)
+        } else {
+            Ok(Direction::from_bits(value.to_bits() / 2))
+        }
+    }
+}
+
+impl From<Direction8> for usize {
+    fn from(value: Direction8) -> Self {
+        value as usize
+    }
+}
+
+impl From<Direction8> for (isize, isize) {
+    fn from(value: Direction8) -> Self {
+        value.delta()
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    /// Accepts compass points (`N`, `NE`, ...) or anything [`Direction`] parses.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Direction8::NE),
+            "SE" => Ok(Direction8::SE),
+            "SW" => Ok(Direction8::SW),
+            "NW" => Ok(Direction8::NW),
+            _ => s
+                .parse::<Direction>()
+                .map(Direction8::from)
+                .map_err(|_| ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl Display for Direction8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction8::N => write!(f, "↑"),
+            Direction8::NE => write!(f, "↗"),
+            Direction8::E => write!(f, "→"),
+            Direction8::SE => write!(f, "↘"),
+            Direction8::S => write!(f, "↓"),
+            Direction8::SW => write!(f, "↙"),
+            Direction8::W => write!(f, "←"),
+            Direction8::NW => write!(f, "↖"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirectionError(pub String);
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid direction: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn turns() {
+        for d in Direction::iter() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(Direction::from_bits(d.to_bits()), d);
+            assert_eq!(Direction::from_delta(d.delta()), Some(d));
+        }
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+
+        for d in Direction8::iter() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.reverse().reverse(), d);
+            assert_eq!(Direction8::from_bits(d.to_bits()), d);
+            let (dr, dc) = d.delta();
+            assert_eq!(d.reverse().delta(), (-dr, -dc));
+        }
+        assert_eq!(Direction8::NW.turn_right(), Direction8::N);
+    }
+
+    #[test]
+    fn conversions() {
+        for d in Direction::iter() {
+            let d8 = Direction8::from(d);
+            assert_eq!(d8.delta(), d.delta());
+            assert_eq!(Direction::try_from(d8), Ok(d));
+        }
+        assert_eq!(Direction::try_from(Direction8::SE), Err(Direction8::SE));
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(
+            "^>v<"
+                .chars()
+                .map(Direction::try_from)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(Direction::ALL.to_vec())
+        );
+        assert_eq!("U".parse(), Ok(Direction::Up));
+        assert_eq!("W".parse(), Ok(Direction::Left));
+        assert_eq!(Direction::try_from(b'D'), Ok(Direction::Down));
+        assert!("x".parse::<Direction>().is_err());
+        assert!("UR".parse::<Direction>().is_err());
+        assert_eq!("SW".parse(), Ok(Direction8::SW));
+        assert_eq!("E".parse(), Ok(Direction8::E));
+    }
+
+    #[test]
+    fn grid_steps() {
+        let grid = Grid::<char>::from("abc\ndef");
+        assert_eq!(grid.step((0, 0), Direction::Right), Some((0, 1)));
+        assert_eq!(grid.step((0, 0), Direction::Up), None);
+        assert_eq!(grid.get_step((0, 1), Direction8::SE), Some(&'f'));
+        assert_eq!(grid.get_step((1, 2), Direction8::E), None);
+    }
+}
@@ -1,9 +1,242 @@
 use std::fmt::Display;
+use std::ops::{Index, IndexMut};
 
+/// A dense 2D grid stored row-major in a single contiguous buffer.
+///
+/// Positions are `(row, column)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
     width: usize,
     height: usize,
-    grid: Vec<Vec<T>>,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` x `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a grid from a row-major buffer.
+    ///
+    /// Panics if `cells.len()` is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "buffer of {} cells does not divide into rows of {}",
+            cells.len(),
+            width
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid from nested rows.
+    ///
+    /// Panics if the rows are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (r, row) in rows.into_iter().enumerate() {
+            assert_eq!(row.len(), width, "row {} has a different width", r);
+            cells.extend(row);
+        }
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// (height, width)
+    #[inline]
+    pub fn dims(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Total number of cells.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, (r, c): (usize, usize)) -> bool {
+        r < self.height && c < self.width
+    }
+
+    /// Converts a position into an index into the underlying buffer.
+    #[inline]
+    pub fn index_of(&self, (r, c): (usize, usize)) -> usize {
+        r * self.width + c
+    }
+
+    /// Converts an index into the underlying buffer back into a position.
+    #[inline]
+    pub fn position_of(&self, index: usize) -> (usize, usize) {
+        (index / self.width, index % self.width)
+    }
+
+    #[inline]
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[self.index_of(pos)])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            let i = self.index_of(pos);
+            Some(&mut self.cells[i])
+        } else {
+            None
+        }
+    }
+
+    /// Converts a signed position into an in-bounds position.
+    #[inline]
+    pub fn checked_pos(&self, (r, c): (isize, isize)) -> Option<(usize, usize)> {
+        if r < 0 || c < 0 {
+            return None;
+        }
+        let pos = (r as usize, c as usize);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Returns `pos` moved by `(dr, dc)` if that stays inside the grid.
+    #[inline]
+    pub fn offset(
+        &self,
+        (r, c): (usize, usize),
+        (dr, dc): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        self.checked_pos((r as isize + dr, c as isize + dc))
+    }
+
+    /// Returns `pos` moved one step in `dir` if that stays inside the grid.
+    #[inline]
+    pub fn step(
+        &self,
+        pos: (usize, usize),
+        dir: impl Into<(isize, isize)>,
+    ) -> Option<(usize, usize)> {
+        self.offset(pos, dir.into())
+    }
+
+    /// Returns the cell one step from `pos` in `dir`.
+    #[inline]
+    pub fn get_step(&self, pos: (usize, usize), dir: impl Into<(isize, isize)>) -> Option<&T> {
+        self.step(pos, dir).map(|pos| &self[pos])
+    }
+
+    #[inline]
+    pub fn get_signed(&self, pos: (isize, isize)) -> Option<&T> {
+        self.checked_pos(pos).map(|pos| &self[pos])
+    }
+
+    #[inline]
+    pub fn get_signed_mut(&mut self, pos: (isize, isize)) -> Option<&mut T> {
+        self.checked_pos(pos).map(|pos| &mut self[pos])
+    }
+
+    /// Returns the cell at `pos` moved by `(dr, dc)`.
+    #[inline]
+    pub fn get_offset(&self, pos: (usize, usize), delta: (isize, isize)) -> Option<&T> {
+        self.offset(pos, delta).map(|pos| &self[pos])
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.cells
+    }
+
+    /// Iterates over the cells in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Iterates over every position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    /// Iterates over `(position, cell)` pairs in row-major order.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns a new grid with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: (usize, usize)) -> &T {
+        assert!(self.contains(pos), "{:?} out of bounds", pos);
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        assert!(self.contains(pos), "{:?} out of bounds", pos);
+        let i = self.index_of(pos);
+        &mut self.cells[i]
+    }
 }
 
 impl<T> From<T> for Grid<char>
@@ -11,18 +244,7 @@ where
     T: Into<String> + AsRef<str>,
 {
     fn from(s: T) -> Self {
-        let grid = s
-            .into()
-            .lines()
-            .map(|line| line.chars().collect::<Vec<char>>())
-            .collect::<Vec<Vec<char>>>();
-        let width = grid[0].len();
-        let height = grid.len();
-        Self {
-            width,
-            height,
-            grid,
-        }
+        Self::parse(s.as_ref()).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -31,22 +253,7 @@ where
     T: Into<String> + AsRef<str>,
 {
     fn from(s: T) -> Self {
-        let grid = s
-            .into()
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).unwrap() as usize)
-                    .collect::<Vec<usize>>()
-            })
-            .collect::<Vec<Vec<usize>>>();
-        let width = grid[0].len();
-        let height = grid.len();
-        Self {
-            width,
-            height,
-            grid,
-        }
+        Self::parse(s.as_ref()).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -55,7 +262,10 @@ where
     T: Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for line in &self.grid {
+        if self.width == 0 {
+            return Ok(());
+        }
+        for line in self.cells.chunks(self.width) {
             for c in line {
                 write!(f, "{}", c)?;
             }
@@ -64,3 +274,49 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "abc\ndef";
+
+    #[test]
+    fn row_major_indexing() {
+        let mut grid = Grid::<char>::from(EXAMPLE);
+        assert_eq!(grid.dims(), (2, 3));
+        assert_eq!(grid[(0, 2)], 'c');
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.as_slice(), &['a', 'b', 'c', 'd', 'e', 'f']);
+        assert_eq!(grid.position_of(4), (1, 1));
+
+        grid[(1, 1)] = 'x';
+        assert_eq!(grid.to_string(), "abc\ndxf\n");
+    }
+
+    #[test]
+    fn checked_access() {
+        let grid = Grid::<char>::from(EXAMPLE);
+        assert_eq!(grid.get((1, 2)), Some(&'f'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get_signed((-1, 0)), None);
+        assert_eq!(grid.get_offset((0, 0), (1, 1)), Some(&'e'));
+        assert_eq!(grid.offset((0, 0), (0, -1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn index_past_row_end() {
+        let grid = Grid::<char>::from(EXAMPLE);
+        let _ = grid[(0, 3)];
+    }
+
+    #[test]
+    fn empty_input() {
+        let grid = Grid::<char>::from("");
+        assert!(grid.is_empty());
+        assert_eq!(grid.dims(), (0, 0));
+        assert_eq!(grid.to_string(), "");
+    }
+}
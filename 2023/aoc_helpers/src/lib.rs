@@ -1,14 +1,35 @@
-use std::fmt::Display;
-
+mod byte_grid;
+mod direction;
 mod grid;
+mod neighbors;
+mod parse;
+mod query;
+mod region;
+mod render;
+mod search;
+mod source;
+mod sparse;
+mod transform;
 
+pub use byte_grid::ByteGrid;
+pub use direction::{Direction, Direction8, ParseDirectionError};
 pub use grid::Grid;
+pub use neighbors::TiledPos;
+pub use parse::{FromCell, GridParseError, GridParseErrorKind};
+pub use query::FindError;
+pub use region::{Connectivity, Region, Regions};
+pub use render::{Color, Render, Style};
+pub use search::{astar, bfs, dijkstra, dijkstra_all, Path, PathCache, ShortestPaths, State};
+#[cfg(feature = "nom")]
+pub use source::NomError;
+pub use source::{ParseError, Source};
+pub use sparse::SparseGrid;
+pub use transform::GridView;
 
 #[cfg(test)]
 mod tests {
     use crate::grid::Grid;
 
-    use super::*;
     const DATA: &str = r"2413432311323
 3215453535623
 3255245654254
@@ -30,6 +51,8 @@ mod tests {
             .collect::<Vec<Vec<char>>>();
 
         let data: Grid<usize> = Grid::from(DATA);
+        assert_eq!(data.height(), example.len());
+        assert_eq!(data.width(), example[0].len());
         println!("{}", data);
         //assert_eq!(result, 4);
     }
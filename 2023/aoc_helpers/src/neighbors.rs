@@ -0,0 +1,143 @@
+use crate::direction::{Direction, Direction8};
+use crate::grid::Grid;
+
+/// A position on a grid that is repeated infinitely in every direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TiledPos {
+    /// Position on the infinite plane.
+    pub global: (isize, isize),
+    /// Matching position inside the grid.
+    pub local: (usize, usize),
+    /// Which copy of the grid `global` falls into, `(0, 0)` being the original.
+    pub tile: (isize, isize),
+}
+
+impl<T> Grid<T> {
+    /// Orthogonal neighbors of `pos` that lie inside the grid.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::iter().filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Orthogonal and diagonal neighbors of `pos` that lie inside the grid.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction8::iter().filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Wraps a signed position around the edges, treating the grid as a torus.
+    #[inline]
+    pub fn wrap(&self, (r, c): (isize, isize)) -> (usize, usize) {
+        (
+            r.rem_euclid(self.height() as isize) as usize,
+            c.rem_euclid(self.width() as isize) as usize,
+        )
+    }
+
+    /// Orthogonal neighbors of `pos`, wrapping around the edges.
+    pub fn wrapping_neighbors4(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::iter().map(move |d| self.wrapping_step(pos, d.delta()))
+    }
+
+    /// Orthogonal and diagonal neighbors of `pos`, wrapping around the edges.
+    pub fn wrapping_neighbors8(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction8::iter().map(move |d| self.wrapping_step(pos, d.delta()))
+    }
+
+    #[inline]
+    fn wrapping_step(&self, (r, c): (usize, usize), (dr, dc): (isize, isize)) -> (usize, usize) {
+        self.wrap((r as isize + dr, c as isize + dc))
+    }
+
+    /// Locates a position on the infinite plane tiled with copies of the grid.
+    #[inline]
+    pub fn tile(&self, global: (isize, isize)) -> TiledPos {
+        let (h, w) = (self.height() as isize, self.width() as isize);
+        TiledPos {
+            global,
+            local: self.wrap(global),
+            tile: (global.0.div_euclid(h), global.1.div_euclid(w)),
+        }
+    }
+
+    /// Returns the cell at a position on the infinitely tiled plane.
+    #[inline]
+    pub fn get_tiled(&self, global: (isize, isize)) -> &T {
+        &self[self.wrap(global)]
+    }
+
+    /// Orthogonal neighbors of `global` on the infinitely tiled plane.
+    pub fn tiled_neighbors4(&self, global: (isize, isize)) -> impl Iterator<Item = TiledPos> + '_ {
+        Direction::iter().map(move |d| self.tiled_step(global, d.delta()))
+    }
+
+    /// Orthogonal and diagonal neighbors of `global` on the infinitely tiled plane.
+    pub fn tiled_neighbors8(&self, global: (isize, isize)) -> impl Iterator<Item = TiledPos> + '_ {
+        Direction8::iter().map(move |d| self.tiled_step(global, d.delta()))
+    }
+
+    #[inline]
+    fn tiled_step(&self, (r, c): (isize, isize), (dr, dc): (isize, isize)) -> TiledPos {
+        self.tile((r + dr, c + dc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "abc\ndef\nghi";
+
+    #[test]
+    fn clipped() {
+        let grid = Grid::<char>::from(EXAMPLE);
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbors8((2, 2)).collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1), (1, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid = Grid::<char>::from(EXAMPLE);
+        assert_eq!(
+            grid.wrapping_neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(2, 0), (0, 1), (1, 0), (0, 2)]
+        );
+        assert_eq!(grid.wrapping_neighbors8((2, 2)).count(), 8);
+        assert_eq!(grid.wrap((-1, 7)), (2, 1));
+    }
+
+    #[test]
+    fn tiled() {
+        let grid = Grid::<char>::from(EXAMPLE);
+        let up = grid.tiled_neighbors4((0, 0)).next().unwrap();
+        assert_eq!(
+            up,
+            TiledPos {
+                global: (-1, 0),
+                local: (2, 0),
+                tile: (-1, 0),
+            }
+        );
+        assert_eq!(grid.tile((7, -4)).tile, (2, -2));
+        assert_eq!(grid.tile((7, -4)).local, (1, 2));
+        assert_eq!(*grid.get_tiled((7, -4)), 'f');
+        assert_eq!(
+            grid.tiled_neighbors8((1, 1))
+                .filter(|p| p.tile == (0, 0))
+                .count(),
+            8
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt::Display;
+
+use crate::grid::Grid;
+
+/// Converts a single character of puzzle input into a grid cell.
+pub trait FromCell: Sized {
+    /// Short description of the accepted characters, used in error messages.
+    const EXPECTED: &'static str;
+
+    fn from_cell(c: char) -> Option<Self>;
+}
+
+impl FromCell for char {
+    const EXPECTED: &'static str = "any character";
+
+    fn from_cell(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+/// Raw ASCII bytes.
+impl FromCell for u8 {
+    const EXPECTED: &'static str = "an ASCII character";
+
+    fn from_cell(c: char) -> Option<Self> {
+        c.is_ascii().then_some(c as u8)
+    }
+}
+
+macro_rules! digit_cell {
+    ($($t:ty),*) => {$(
+        /// Decimal digits.
+        impl FromCell for $t {
+            const EXPECTED: &'static str = "a digit";
+
+            fn from_cell(c: char) -> Option<Self> {
+                c.to_digit(10).map(|d| d as $t)
+            }
+        }
+    )*};
+}
+
+digit_cell!(u32, u64, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseErrorKind {
+    /// A row has a different length than the first one.
+    Ragged { expected: usize, found: usize },
+    /// A character was rejected by the cell parser.
+    InvalidCell { found: char, expected: &'static str },
+}
+
+/// Why and where a grid failed to parse. Lines and columns are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: GridParseErrorKind,
+}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            GridParseErrorKind::Ragged { expected, found } => {
+                write!(f, "row has {} cells, expected {}", found, expected)
+            }
+            GridParseErrorKind::InvalidCell { found, expected } => {
+                write!(f, "found {:?}, expected {}", found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+impl<T: FromCell> Grid<T> {
+    /// Parses one cell per character, one row per line.
+    pub fn parse(input: &str) -> Result<Self, GridParseError> {
+        Self::parse_with_expected(input, T::EXPECTED, T::from_cell)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses one cell per character using `f`, one row per line.
+    pub fn parse_with(
+        input: &str,
+        f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridParseError> {
+        Self::parse_with_expected(input, "a valid cell", f)
+    }
+
+    fn parse_with_expected(
+        input: &str,
+        expected: &'static str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridParseError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+
+        for (r, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (c, ch) in line.chars().enumerate() {
+                let cell = f(ch).ok_or(GridParseError {
+                    line: r + 1,
+                    column: c + 1,
+                    kind: GridParseErrorKind::InvalidCell {
+                        found: ch,
+                        expected,
+                    },
+                })?;
+                cells.push(cell);
+            }
+
+            let found = cells.len() - start;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridParseError {
+                        line: r + 1,
+                        column: expected.min(found) + 1,
+                        kind: GridParseErrorKind::Ragged { expected, found },
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(Self::from_vec(width.unwrap_or(0), cells))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits() {
+        let grid = Grid::<u32>::parse("123\n456\n").unwrap();
+        assert_eq!(grid.dims(), (2, 3));
+        assert_eq!(grid[(1, 2)], 6);
+
+        let err = Grid::<usize>::parse("123\n4x6").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: found 'x', expected a digit"
+        );
+    }
+
+    #[test]
+    fn ragged() {
+        let err = Grid::<char>::parse("abc\nde\nfgh").unwrap_err();
+        assert_eq!(
+            err.kind,
+            GridParseErrorKind::Ragged {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn closure() {
+        let grid = Grid::parse_with("#.\n.#", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(grid.as_slice(), &[true, false, false, true]);
+        assert!(Grid::parse_with("#?", |c| (c == '#').then_some(())).is_err());
+    }
+
+    #[test]
+    fn empty() {
+        let grid = Grid::<u8>::parse("").unwrap();
+        assert!(grid.is_empty());
+        assert_eq!(grid.dims(), (0, 0));
+    }
+}
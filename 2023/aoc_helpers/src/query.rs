@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::grid::Grid;
+
+/// Returned by [`Grid::find_unique`] when a cell isn't present exactly once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FindError {
+    NotFound,
+    /// The first two matching positions.
+    Duplicate((usize, usize), (usize, usize)),
+}
+
+impl Display for FindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FindError::NotFound => write!(f, "no matching cell"),
+            FindError::Duplicate(a, b) => write!(f, "matching cells at both {:?} and {:?}", a, b),
+        }
+    }
+}
+
+impl std::error::Error for FindError {}
+
+impl<T> Grid<T> {
+    /// Position of the first cell, in row-major order, that satisfies `pred`.
+    pub fn find_by(&self, pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter().position(pred).map(|i| self.position_of(i))
+    }
+
+    /// Position of the first cell, in row-major order, equal to `value`.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.find_by(|c| c == value)
+    }
+
+    /// Positions of every cell that satisfies `pred`, in row-major order.
+    pub fn find_all_by<'a>(
+        &'a self,
+        mut pred: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.enumerate()
+            .filter_map(move |(pos, c)| pred(c).then_some(pos))
+    }
+
+    /// Positions of every cell equal to `value`, in row-major order.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.find_all_by(move |c| c == value)
+    }
+
+    /// Position of the only cell that satisfies `pred`.
+    pub fn find_unique_by(
+        &self,
+        mut pred: impl FnMut(&T) -> bool,
+    ) -> Result<(usize, usize), FindError> {
+        let mut matches = self.find_all_by(&mut pred);
+        match (matches.next(), matches.next()) {
+            (None, _) => Err(FindError::NotFound),
+            (Some(pos), None) => Ok(pos),
+            (Some(a), Some(b)) => Err(FindError::Duplicate(a, b)),
+        }
+    }
+
+    /// Position of the only cell equal to `value`.
+    pub fn find_unique(&self, value: &T) -> Result<(usize, usize), FindError>
+    where
+        T: PartialEq,
+    {
+        self.find_unique_by(|c| c == value)
+    }
+
+    pub fn count_by(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.iter().filter(|c| pred(c)).count()
+    }
+
+    pub fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.count_by(|c| c == value)
+    }
+
+    /// Groups the positions of every cell that satisfies `keep` by cell value.
+    ///
+    /// Positions within a group are in row-major order.
+    pub fn group_positions(
+        &self,
+        mut keep: impl FnMut(&T) -> bool,
+    ) -> HashMap<T, Vec<(usize, usize)>>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let mut groups: HashMap<T, Vec<(usize, usize)>> = HashMap::new();
+        for (pos, c) in self.enumerate() {
+            if keep(c) {
+                groups.entry(c.clone()).or_default().push(pos);
+            }
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "S.a\n.a.\nb.E";
+
+    #[test]
+    fn find() {
+        let grid = Grid::<char>::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.find(&'E'), Some((2, 2)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(grid.find_by(|c| c.is_ascii_lowercase()), Some((0, 2)));
+        assert_eq!(
+            grid.find_all(&'a').collect::<Vec<_>>(),
+            vec![(0, 2), (1, 1)]
+        );
+        assert_eq!(grid.count(&'.'), 4);
+        assert_eq!(grid.count_by(|c| c.is_ascii_uppercase()), 2);
+    }
+
+    #[test]
+    fn unique() {
+        let grid = Grid::<char>::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.find_unique(&'S'), Ok((0, 0)));
+        assert_eq!(grid.find_unique(&'x'), Err(FindError::NotFound));
+        assert_eq!(
+            grid.find_unique(&'a'),
+            Err(FindError::Duplicate((0, 2), (1, 1)))
+        );
+    }
+
+    #[test]
+    fn groups() {
+        let grid = Grid::<char>::parse(EXAMPLE).unwrap();
+        let groups = grid.group_positions(|&c| c.is_ascii_lowercase());
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&'a'], vec![(0, 2), (1, 1)]);
+        assert_eq!(groups[&'b'], vec![(2, 0)]);
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::VecDeque;
+
+use crate::direction::{Direction, Direction8};
+use crate::grid::Grid;
+
+/// Which neighbors count as adjacent when growing a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Orthogonal neighbors only.
+    Four,
+    /// Orthogonal and diagonal neighbors.
+    Eight,
+}
+
+impl Connectivity {
+    fn deltas(self) -> impl Iterator<Item = (isize, isize)> {
+        Direction8::iter()
+            .filter(move |d| self == Connectivity::Eight || !d.is_diagonal())
+            .map(Direction8::delta)
+    }
+}
+
+/// A connected group of cells found by [`Grid::label_regions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Index of this region in [`Regions::regions`] and its value in
+    /// [`Regions::labels`].
+    pub label: usize,
+    /// Cells in the order they were reached, starting from the first cell in
+    /// row-major order.
+    pub cells: Vec<(usize, usize)>,
+    /// Number of cell edges that border a cell outside the region or the edge
+    /// of the grid.
+    pub perimeter: usize,
+    /// Number of straight sides of the region's outline, holes included.
+    pub sides: usize,
+    /// Inclusive `(min, max)` corners of the bounding box.
+    pub bounds: ((usize, usize), (usize, usize)),
+}
+
+impl Region {
+    #[inline]
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// Every region of a grid, together with a map from position to region label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    /// The region containing `pos`.
+    #[inline]
+    pub fn region_at(&self, pos: (usize, usize)) -> &Region {
+        &self.regions[self.labels[pos]]
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Region> {
+        self.regions.iter()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Splits the grid into regions of equal, connected cells.
+    pub fn label_regions(&self, connectivity: Connectivity) -> Regions
+    where
+        T: PartialEq,
+    {
+        self.label_regions_by(connectivity, |a, b| a == b)
+    }
+
+    /// Splits the grid into regions, joining adjacent cells `a` and `b`
+    /// whenever `joins(a, b)` holds.
+    ///
+    /// Every cell ends up in exactly one region. Perimeter and sides are
+    /// measured along orthogonal edges whatever the connectivity.
+    pub fn label_regions_by(
+        &self,
+        connectivity: Connectivity,
+        mut joins: impl FnMut(&T, &T) -> bool,
+    ) -> Regions {
+        const UNLABELLED: usize = usize::MAX;
+        let mut labels = Grid::new(self.width(), self.height(), UNLABELLED);
+        let mut regions = Vec::new();
+
+        for start in self.positions() {
+            if labels[start] != UNLABELLED {
+                continue;
+            }
+
+            let label = regions.len();
+            labels[start] = label;
+            let mut cells = vec![start];
+            let mut next = 0;
+            while let Some(&pos) = cells.get(next) {
+                next += 1;
+                for delta in connectivity.deltas() {
+                    let Some(n) = self.offset(pos, delta) else {
+                        continue;
+                    };
+                    if labels[n] == UNLABELLED && joins(&self[pos], &self[n]) {
+                        labels[n] = label;
+                        cells.push(n);
+                    }
+                }
+            }
+
+            regions.push(Region {
+                label,
+                cells,
+                perimeter: 0,
+                sides: 0,
+                bounds: (start, start),
+            });
+        }
+
+        for region in &mut regions {
+            measure(region, &labels);
+        }
+
+        Regions { labels, regions }
+    }
+
+    /// Every position reachable from `start` through cells for which
+    /// `passable` holds. `start` itself is included only if it is passable.
+    pub fn flood_fill(
+        &self,
+        start: (usize, usize),
+        connectivity: Connectivity,
+        mut passable: impl FnMut((usize, usize), &T) -> bool,
+    ) -> Grid<bool> {
+        let mut filled = Grid::new(self.width(), self.height(), false);
+        if !passable(start, &self[start]) {
+            return filled;
+        }
+
+        filled[start] = true;
+        let mut queue = VecDeque::from([start]);
+        while let Some(pos) = queue.pop_front() {
+            for delta in connectivity.deltas() {
+                let Some(n) = self.offset(pos, delta) else {
+                    continue;
+                };
+                if !filled[n] && passable(n, &self[n]) {
+                    filled[n] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+        filled
+    }
+}
+
+/// Fills in the perimeter, side count and bounding box of `region`.
+fn measure(region: &mut Region, labels: &Grid<usize>) {
+    let inside = |pos: (usize, usize), delta: (isize, isize)| {
+        labels.get_offset(pos, delta) == Some(&region.label)
+    };
+
+    let (mut min, mut max) = region.bounds;
+    let (mut perimeter, mut corners) = (0, 0);
+    for &pos in &region.cells {
+        min = (min.0.min(pos.0), min.1.min(pos.1));
+        max = (max.0.max(pos.0), max.1.max(pos.1));
+
+        perimeter += Direction::iter()
+            .filter(|&d| !inside(pos, d.delta()))
+            .count();
+
+        // A region has as many sides as its outline has corners.
+        for d in Direction::iter() {
+            let a = inside(pos, d.delta());
+            let b = inside(pos, d.turn_right().delta());
+            let diagonal = inside(pos, Direction8::from(d).turn_right().delta());
+            if (!a && !b) || (a && b && !diagonal) {
+                corners += 1;
+            }
+        }
+    }
+
+    region.bounds = (min, max);
+    region.perimeter = perimeter;
+    region.sides = corners;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions() {
+        let grid = Grid::<char>::parse("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let regions = grid.label_regions(Connectivity::Four);
+        assert_eq!(regions.regions.len(), 5);
+
+        let c = regions.region_at((1, 2));
+        assert_eq!((c.area(), c.perimeter, c.sides), (4, 10, 8));
+        assert_eq!(c.bounds, ((1, 2), (3, 3)));
+
+        let a = regions.region_at((0, 0));
+        assert_eq!((a.area(), a.perimeter, a.sides), (4, 10, 4));
+        assert_eq!(regions.labels[(0, 3)], a.label);
+    }
+
+    #[test]
+    fn holes() {
+        let grid = Grid::<char>::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO").unwrap();
+        let regions = grid.label_regions(Connectivity::Four);
+        let outer = regions.region_at((0, 0));
+        assert_eq!((outer.area(), outer.perimeter, outer.sides), (21, 36, 20));
+    }
+
+    #[test]
+    fn diagonal() {
+        let grid = Grid::<char>::parse("#.\n.#").unwrap();
+        assert_eq!(grid.label_regions(Connectivity::Four).regions.len(), 4);
+        let regions = grid.label_regions(Connectivity::Eight);
+        assert_eq!(regions.regions.len(), 2);
+        assert_eq!(regions.region_at((1, 1)).area(), 2);
+    }
+
+    #[test]
+    fn fill() {
+        let grid = Grid::<char>::parse("..#..\n.#...\n#....").unwrap();
+        let filled = grid.flood_fill((0, 0), Connectivity::Four, |_, &c| c == '.');
+        assert_eq!(filled.count(&true), 3);
+        let filled = grid.flood_fill((0, 0), Connectivity::Eight, |_, &c| c == '.');
+        assert_eq!(filled.count(&true), 12);
+        let filled = grid.flood_fill((0, 4), Connectivity::Four, |_, &c| c == '.');
+        assert_eq!(filled.count(&true), 9);
+        assert!(!grid.flood_fill((0, 2), Connectivity::Four, |_, &c| c == '.')[(0, 2)]);
+    }
+}
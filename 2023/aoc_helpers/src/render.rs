@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+
+use crate::direction::Direction;
+use crate::grid::Grid;
+
+/// ANSI foreground colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    const fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+/// How an overlay changes the cells it covers. Unset fields leave the cell, or
+/// an earlier overlay, as it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    pub glyph: Option<char>,
+    pub color: Option<Color>,
+}
+
+impl Style {
+    /// Replaces the cell with `glyph`.
+    pub const fn glyph(glyph: char) -> Self {
+        Self {
+            glyph: Some(glyph),
+            color: None,
+        }
+    }
+
+    /// Keeps the cell but draws it in `color`.
+    pub const fn color(color: Color) -> Self {
+        Self {
+            glyph: None,
+            color: Some(color),
+        }
+    }
+
+    pub const fn with_color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    fn merge(self, over: Style) -> Style {
+        Style {
+            glyph: over.glyph.or(self.glyph),
+            color: over.color.or(self.color),
+        }
+    }
+}
+
+/// Draws a [`Grid`] with overlays on top, for debugging and snapshot tests.
+///
+/// Created by [`Grid::render`] or [`Grid::render_with`]. Overlays are applied
+/// in the order they were added, so later ones win. Each cell should format
+/// as a single character for the ruler to line up.
+pub struct Render<'a, T> {
+    grid: &'a Grid<T>,
+    format: Box<dyn Fn(&T) -> String + 'a>,
+    overlay: HashMap<(usize, usize), Style>,
+    ruler: bool,
+}
+
+impl<T> Grid<T> {
+    /// Renders each cell with its `Display` impl.
+    pub fn render(&self) -> Render<'_, T>
+    where
+        T: Display,
+    {
+        self.render_with(|c: &T| c.to_string())
+    }
+
+    /// Renders each cell with `format`.
+    pub fn render_with<'a, D: Display>(&'a self, format: impl Fn(&T) -> D + 'a) -> Render<'a, T> {
+        Render {
+            grid: self,
+            format: Box::new(move |c| format(c).to_string()),
+            overlay: HashMap::new(),
+            ruler: false,
+        }
+    }
+}
+
+impl<T> Render<'_, T> {
+    /// Applies `style` to every position in `positions`. Positions outside the
+    /// grid are ignored.
+    pub fn overlay(
+        mut self,
+        positions: impl IntoIterator<Item = (usize, usize)>,
+        style: Style,
+    ) -> Self {
+        for pos in positions {
+            self.add(pos, style);
+        }
+        self
+    }
+
+    /// Draws `path` with an arrow on each step pointing to the next one.
+    ///
+    /// The last position, and any step that isn't to an orthogonal neighbor,
+    /// uses `style`'s glyph instead.
+    pub fn path(mut self, path: impl IntoIterator<Item = (usize, usize)>, style: Style) -> Self {
+        let mut path = path.into_iter().peekable();
+        while let Some(pos) = path.next() {
+            let arrow = path.peek().and_then(|&(r, c)| {
+                Direction::from_delta((r as isize - pos.0 as isize, c as isize - pos.1 as isize))
+            });
+            self.add(
+                pos,
+                Style {
+                    glyph: arrow.map(Direction::arrow).or(style.glyph),
+                    ..style
+                },
+            );
+        }
+        self
+    }
+
+    /// Prints column numbers above and row numbers to the left of the grid.
+    pub fn ruler(mut self, ruler: bool) -> Self {
+        self.ruler = ruler;
+        self
+    }
+
+    fn add(&mut self, pos: (usize, usize), style: Style) {
+        if self.grid.contains(pos) {
+            let cell = self.overlay.entry(pos).or_default();
+            *cell = cell.merge(style);
+        }
+    }
+}
+
+impl<T> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label_width = self.grid.height().saturating_sub(1).to_string().len();
+
+        if self.ruler {
+            if self.grid.width() > 10 {
+                write!(f, "{:label_width$} ", "")?;
+                for c in 0..self.grid.width() {
+                    match c % 10 {
+                        0 => write!(f, "{}", c / 10 % 10)?,
+                        _ => f.write_char(' ')?,
+                    }
+                }
+                writeln!(f)?;
+            }
+            write!(f, "{:label_width$} ", "")?;
+            for c in 0..self.grid.width() {
+                write!(f, "{}", c % 10)?;
+            }
+            writeln!(f)?;
+        }
+
+        for r in 0..self.grid.height() {
+            if self.ruler {
+                write!(f, "{:>label_width$} ", r)?;
+            }
+            for c in 0..self.grid.width() {
+                let style = self.overlay.get(&(r, c)).copied().unwrap_or_default();
+                let cell = match style.glyph {
+                    Some(glyph) => glyph.to_string(),
+                    None => (self.format)(&self.grid[(r, c)]),
+                };
+                match style.color {
+                    Some(color) => write!(f, "\x1b[{}m{}\x1b[0m", color.code(), cell)?,
+                    None => f.write_str(&cell)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlays() {
+        let grid = Grid::<char>::parse("....\n.#..\n....").unwrap();
+        let out = grid
+            .render()
+            .overlay([(1, 1), (5, 5)], Style::glyph('X'))
+            .path([(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)], Style::glyph('E'))
+            .to_string();
+        assert_eq!(out, "→→↓.\n.X↓.\n..E.\n");
+    }
+
+    #[test]
+    fn formatter_and_colors() {
+        let grid = Grid::<u32>::parse("12\n34").unwrap();
+        let out = grid
+            .render_with(|&n| if n % 2 == 0 { '#' } else { '.' })
+            .overlay([(0, 0)], Style::color(Color::Red))
+            .overlay([(0, 0), (1, 1)], Style::glyph('@'))
+            .to_string();
+        assert_eq!(out, "\x1b[31m@\x1b[0m#\n.@\n");
+    }
+
+    #[test]
+    fn ruler() {
+        let grid = Grid::new(12, 11, '.');
+        let out = grid.render().ruler(true).to_string();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "   0         1 ");
+        assert_eq!(lines[1], "   012345678901");
+        assert_eq!(lines[2], " 0 ............");
+        assert_eq!(lines[12], "10 ............");
+    }
+}
@@ -0,0 +1,422 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A node in a search space. Implemented for every `Clone + Eq + Hash` type,
+/// so positions, tuples and small structs can be searched directly.
+pub trait State: Clone + Eq + Hash {}
+
+impl<T: Clone + Eq + Hash> State for T {}
+
+/// A cheapest path found by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    /// Every state from the start to the goal, both included.
+    pub states: Vec<S>,
+}
+
+impl<S: State> Path<S> {
+    pub fn contains(&self, state: &S) -> bool {
+        self.states.contains(state)
+    }
+
+    pub fn goal(&self) -> &S {
+        self.states.last().expect("paths are never empty")
+    }
+}
+
+const NO_PARENT: usize = usize::MAX;
+
+struct Node<S> {
+    state: S,
+    cost: usize,
+    parent: usize,
+    closed: bool,
+}
+
+/// Interns states so the queues only have to hold indices.
+struct Arena<S> {
+    nodes: Vec<Node<S>>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: State> Arena<S> {
+    fn new(start: S) -> Self {
+        let mut arena = Self {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+        };
+        arena.insert(start, 0, NO_PARENT);
+        arena
+    }
+
+    fn insert(&mut self, state: S, cost: usize, parent: usize) -> usize {
+        let i = self.nodes.len();
+        self.index.insert(state.clone(), i);
+        self.nodes.push(Node {
+            state,
+            cost,
+            parent,
+            closed: false,
+        });
+        i
+    }
+
+    fn path(&self, mut i: usize) -> Path<S> {
+        let cost = self.nodes[i].cost;
+        let mut states = Vec::new();
+        while i != NO_PARENT {
+            states.push(self.nodes[i].state.clone());
+            i = self.nodes[i].parent;
+        }
+        states.reverse();
+        Path { cost, states }
+    }
+}
+
+/// Breadth-first search, where every move costs 1.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: State,
+    I: IntoIterator<Item = S>,
+{
+    let mut arena = Arena::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        let state = arena.nodes[i].state.clone();
+        if is_goal(&state) {
+            return Some(arena.path(i));
+        }
+
+        let cost = arena.nodes[i].cost + 1;
+        for next in successors(&state) {
+            if !arena.index.contains_key(&next) {
+                queue.push_back(arena.insert(next, cost, i));
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm. `successors` yields each neighboring state with the
+/// cost of moving there.
+pub fn dijkstra<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: State,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(start, successors, |_| 0, is_goal)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost, or the
+/// path found may not be the cheapest.
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: State,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    let mut arena = Arena::new(start);
+
+    while let Some(Reverse((_, i))) = heap.pop() {
+        if arena.nodes[i].closed {
+            continue;
+        }
+        arena.nodes[i].closed = true;
+
+        let state = arena.nodes[i].state.clone();
+        if is_goal(&state) {
+            return Some(arena.path(i));
+        }
+
+        let cost = arena.nodes[i].cost;
+        for (next, step) in successors(&state) {
+            let cost = cost + step;
+            let j = match arena.index.get(&next) {
+                Some(&j) if cost < arena.nodes[j].cost => {
+                    let node = &mut arena.nodes[j];
+                    node.cost = cost;
+                    node.parent = i;
+                    node.closed = false;
+                    j
+                }
+                Some(_) => continue,
+                None => arena.insert(next, cost, i),
+            };
+            heap.push(Reverse((cost + heuristic(&arena.nodes[j].state), j)));
+        }
+    }
+
+    None
+}
+
+/// Every cheapest path to the goal, stored as a DAG of predecessors.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<S> {
+    cost: usize,
+    states: Vec<S>,
+    index: HashMap<S, usize>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<S: State> ShortestPaths<S> {
+    pub fn cost(&self) -> usize {
+        self.cost
+    }
+
+    /// Every goal state reached at the optimal cost.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&i| &self.states[i])
+    }
+
+    /// The states that can come immediately before `state` on a cheapest
+    /// path to it.
+    pub fn predecessors(&self, state: &S) -> impl Iterator<Item = &S> {
+        self.index
+            .get(state)
+            .map_or(&[][..], |&i| &self.predecessors[i])
+            .iter()
+            .map(|&j| &self.states[j])
+    }
+
+    /// One of the cheapest paths.
+    pub fn path(&self) -> Path<S> {
+        let mut i = self.goals[0];
+        let mut states = vec![self.states[i].clone()];
+        while let Some(&j) = self.predecessors[i].first() {
+            states.push(self.states[j].clone());
+            i = j;
+        }
+        states.reverse();
+        Path {
+            cost: self.cost,
+            states,
+        }
+    }
+
+    /// Every state that lies on at least one cheapest path, each once.
+    pub fn states_on_paths(&self) -> impl Iterator<Item = &S> {
+        let mut seen = vec![false; self.states.len()];
+        let mut stack = self.goals.clone();
+        for &g in &stack {
+            seen[g] = true;
+        }
+        let mut order = Vec::new();
+        while let Some(i) = stack.pop() {
+            order.push(i);
+            for &j in &self.predecessors[i] {
+                if !seen[j] {
+                    seen[j] = true;
+                    stack.push(j);
+                }
+            }
+        }
+        order.into_iter().map(|i| &self.states[i])
+    }
+}
+
+/// Dijkstra's algorithm that keeps every cheapest path instead of just one,
+/// for questions like "which cells lie on any best route".
+pub fn dijkstra_all<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPaths<S>>
+where
+    S: State,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut arena = Arena::new(start);
+    let mut predecessors = vec![Vec::new()];
+    let mut heap = BinaryHeap::from([Reverse((0, 0))]);
+    let mut best = None;
+    let mut goals = Vec::new();
+
+    while let Some(Reverse((cost, i))) = heap.pop() {
+        if arena.nodes[i].closed {
+            continue;
+        }
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+        arena.nodes[i].closed = true;
+
+        let state = arena.nodes[i].state.clone();
+        if is_goal(&state) {
+            best = Some(cost);
+            goals.push(i);
+            continue;
+        }
+
+        for (next, step) in successors(&state) {
+            let cost = cost + step;
+            match arena.index.get(&next) {
+                Some(&j) => {
+                    let node = &mut arena.nodes[j];
+                    if cost < node.cost {
+                        node.cost = cost;
+                        predecessors[j] = vec![i];
+                        heap.push(Reverse((cost, j)));
+                    } else if cost == node.cost {
+                        predecessors[j].push(i);
+                    }
+                }
+                None => {
+                    heap.push(Reverse((cost, arena.insert(next, cost, i))));
+                    predecessors.push(vec![i]);
+                }
+            }
+        }
+    }
+
+    Some(ShortestPaths {
+        cost: best?,
+        states: arena.nodes.into_iter().map(|n| n.state).collect(),
+        index: arena.index,
+        predecessors,
+        goals,
+    })
+}
+
+/// Keeps the last path found so that it only has to be searched for again
+/// once one of its states becomes blocked.
+#[derive(Debug, Clone)]
+pub struct PathCache<S> {
+    path: Option<Path<S>>,
+}
+
+impl<S> Default for PathCache<S> {
+    fn default() -> Self {
+        Self { path: None }
+    }
+}
+
+impl<S: State> PathCache<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached path if none of its states are `blocked`, and the
+    /// result of `search` otherwise.
+    pub fn get_or_search(
+        &mut self,
+        mut blocked: impl FnMut(&S) -> bool,
+        search: impl FnOnce() -> Option<Path<S>>,
+    ) -> Option<&Path<S>> {
+        let stale = self
+            .path
+            .as_ref()
+            .is_none_or(|path| path.states.iter().any(&mut blocked));
+        if stale {
+            self.path = search();
+        }
+        self.path.as_ref()
+    }
+
+    pub fn clear(&mut self) {
+        self.path = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+S..#
+.#..
+...E";
+
+    fn open_neighbors(grid: &Grid<char>) -> impl Fn(&(usize, usize)) -> Vec<(usize, usize)> + '_ {
+        |&pos| grid.neighbors4(pos).filter(|&n| grid[n] != '#').collect()
+    }
+
+    #[test]
+    fn shortest() {
+        let grid = Grid::<char>::parse(MAZE).unwrap();
+        let goal = grid.find(&'E').unwrap();
+
+        let path = bfs((0, 0), open_neighbors(&grid), |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states.len(), 6);
+        assert_eq!(path.goal(), &goal);
+
+        let weighted = |p: &(usize, usize)| {
+            open_neighbors(&grid)(p).into_iter().map(|n| match n {
+                (1, 0) => (n, 3),
+                (1, 2) => (n, 10),
+                _ => (n, 1),
+            })
+        };
+        let path = dijkstra((0, 0), weighted, |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 7);
+        assert!(path.contains(&(2, 0)));
+
+        let manhattan = |&(r, c): &(usize, usize)| goal.0.abs_diff(r) + goal.1.abs_diff(c);
+        let path = astar((0, 0), weighted, manhattan, |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 7);
+
+        assert!(bfs((0, 0), open_neighbors(&grid), |&p| p == (0, 3)).is_none());
+    }
+
+    #[test]
+    fn all_paths() {
+        let grid = Grid::<char>::parse(MAZE).unwrap();
+        let goal = grid.find(&'E').unwrap();
+        let steps = |p: &(usize, usize)| open_neighbors(&grid)(p).into_iter().map(|n| (n, 1));
+
+        let paths = dijkstra_all((0, 0), steps, |&p| p == goal).unwrap();
+        assert_eq!(paths.cost(), 5);
+        assert_eq!(paths.goals().collect::<Vec<_>>(), vec![&goal]);
+        assert_eq!(paths.path().states.len(), 6);
+        assert_eq!(paths.predecessors(&goal).count(), 2);
+        // both ways around the wall are equally short
+        assert_eq!(paths.states_on_paths().count(), 10);
+    }
+
+    #[test]
+    fn cache() {
+        let mut grid = Grid::<char>::parse(MAZE).unwrap();
+        let mut cache = PathCache::new();
+        let mut searches = 0;
+
+        let mut query = |grid: &Grid<char>, blocked: (usize, usize), searches: &mut usize| {
+            cache
+                .get_or_search(
+                    |&p| p == blocked,
+                    || {
+                        *searches += 1;
+                        bfs((0, 0), open_neighbors(grid), |&p| p == (2, 3))
+                    },
+                )
+                .map(|path| path.cost)
+        };
+
+        assert_eq!(query(&grid, (0, 0), &mut searches), Some(5));
+        assert_eq!(query(&grid, (0, 3), &mut searches), Some(5));
+        assert_eq!(searches, 1);
+
+        grid[(0, 1)] = '#';
+        grid[(1, 0)] = '#';
+        assert_eq!(query(&grid, (0, 1), &mut searches), None);
+        assert_eq!(searches, 2);
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::parse::{GridParseError, GridParseErrorKind};
+
+/// Why and where some puzzle input failed to parse. Lines and columns are
+/// 1-based; both are 0 if the location isn't known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The start of the offending text, up to the end of its line.
+    pub found: String,
+    /// What should have been there instead.
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}, column {}: ", self.line, self.column)?;
+        }
+        if self.found.is_empty() {
+            write!(f, "found nothing, expected {}", self.expected)
+        } else {
+            write!(f, "found {:?}, expected {}", self.found, self.expected)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<GridParseError> for ParseError {
+    fn from(err: GridParseError) -> Self {
+        let (found, expected) = match err.kind {
+            GridParseErrorKind::Ragged { expected, found } => {
+                (format!("{found} cells"), format!("{expected} cells"))
+            }
+            GridParseErrorKind::InvalidCell { found, expected } => {
+                (found.to_string(), expected.to_string())
+            }
+        };
+        ParseError {
+            line: err.line,
+            column: err.column,
+            found,
+            expected,
+        }
+    }
+}
+
+/// The whole of a puzzle input, which turns any slice of it into a
+/// [`ParseError`] that says where the slice is. Its helpers stand in for the
+/// `str` methods that return `Option`s, so parsers can use `?`:
+///
+/// ```
+/// # use aoc_helpers::{ParseError, Source};
+/// fn parse(input: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+///     let source = Source::new(input);
+///     input
+///         .lines()
+///         .map(|line| {
+///             let (start, end) = source.split_once(line, "-")?;
+///             Ok((source.parse(start)?, source.parse(end)?))
+///         })
+///         .collect()
+/// }
+///
+/// assert_eq!(parse("1-2\n3-4").unwrap(), [(1, 2), (3, 4)]);
+/// let err = parse("1-2\n3-x").unwrap_err();
+/// assert_eq!(err.to_string(), r#"line 2, column 3: found "x", expected u32"#);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Source { text }
+    }
+
+    /// The line and column `s` starts at, if it's a slice of the source.
+    pub fn position(&self, s: &str) -> Option<(usize, usize)> {
+        let offset = (s.as_ptr() as usize).checked_sub(self.text.as_ptr() as usize)?;
+        let before = self.text.get(..offset)?;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before.matches('\n').count() + 1;
+        Some((line, before[line_start..].chars().count() + 1))
+    }
+
+    /// An error for `found`, a slice of the source.
+    pub fn error(&self, found: &str, expected: impl Into<String>) -> ParseError {
+        let (line, column) = self.position(found).unwrap_or_default();
+        ParseError {
+            line,
+            column,
+            found: found.lines().next().unwrap_or_default().to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// `s.split_once(delimiter)`, or an error for `s`.
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter)
+            .ok_or_else(|| self.error(s, format!("{delimiter:?} somewhere")))
+    }
+
+    /// `s.strip_prefix(prefix)`, or an error for `s`.
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("{prefix:?} first")))
+    }
+
+    /// `s.parse()`, or an error for `s` that names the type it should have
+    /// been.
+    pub fn parse<T: FromStr>(&self, s: &'a str) -> Result<T, ParseError> {
+        s.parse().map_err(|_| {
+            let name = std::any::type_name::<T>();
+            self.error(s, name.rsplit("::").next().unwrap_or(name))
+        })
+    }
+
+    /// Runs a nom parser over `s`, which it has to consume all of but for
+    /// trailing whitespace. Plain nom errors only say which kind of parser
+    /// failed, so parsers should use `VerboseError` and name what they expect
+    /// with `context`.
+    #[cfg(feature = "nom")]
+    pub fn nom<T, E: NomError<'a>>(
+        &self,
+        s: &'a str,
+        mut parser: impl nom::Parser<&'a str, T, E>,
+    ) -> Result<T, ParseError> {
+        match parser.parse(s) {
+            Ok((rest, _)) if !rest.trim().is_empty() => {
+                Err(self.error(rest.trim_start(), "the end of the input"))
+            }
+            Ok((_, value)) => Ok(value),
+            Err(nom::Err::Incomplete(_)) => Err(self.error(&s[s.len()..], "more input")),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+                let (found, expected) = e.failure();
+                Err(self.error(found, expected))
+            }
+        }
+    }
+}
+
+/// A nom error that can say where it happened and what was expected there.
+#[cfg(feature = "nom")]
+pub trait NomError<'a> {
+    fn failure(&self) -> (&'a str, String);
+}
+
+#[cfg(feature = "nom")]
+impl<'a> NomError<'a> for nom::error::Error<&'a str> {
+    fn failure(&self) -> (&'a str, String) {
+        (self.input, nom_expected(self.code))
+    }
+}
+
+/// Uses the innermost `context` as what was expected, if there is one.
+#[cfg(feature = "nom")]
+impl<'a> NomError<'a> for nom::error::VerboseError<&'a str> {
+    fn failure(&self) -> (&'a str, String) {
+        use nom::error::VerboseErrorKind;
+
+        let Some(&(input, ref kind)) = self.errors.first() else {
+            return ("", "valid input".to_string());
+        };
+        let context = self.errors.iter().find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(context.to_string()),
+            _ => None,
+        });
+        let expected = context.unwrap_or_else(|| match kind {
+            VerboseErrorKind::Char(c) => format!("{c:?}"),
+            VerboseErrorKind::Nom(kind) => nom_expected(*kind),
+            VerboseErrorKind::Context(context) => context.to_string(),
+        });
+        (input, expected)
+    }
+}
+
+/// What the nom parsers that fail with `kind` look for.
+#[cfg(feature = "nom")]
+fn nom_expected(kind: nom::error::ErrorKind) -> String {
+    use nom::error::ErrorKind;
+
+    match kind {
+        ErrorKind::Tag => "a different tag".to_string(),
+        ErrorKind::Char => "a different character".to_string(),
+        ErrorKind::Digit => "a number".to_string(),
+        ErrorKind::Alpha => "a word".to_string(),
+        ErrorKind::MapRes => "a value in range".to_string(),
+        ErrorKind::Eof => "the end of the input".to_string(),
+        kind => kind.description().to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    const INPUT: &str = "p=0,4 v=3,-3\np=6,3 v=-1,-3\np=10,3 v=-1,y";
+
+    #[test]
+    fn locates_slices() {
+        let source = Source::new(INPUT);
+        let third = INPUT.lines().nth(2).unwrap();
+        assert_eq!(source.position(INPUT), Some((1, 1)));
+        assert_eq!(source.position(third), Some((3, 1)));
+        assert_eq!(source.position(&third[12..]), Some((3, 13)));
+        assert_eq!(source.position(&INPUT[INPUT.len()..]), Some((3, 14)));
+        assert_eq!(source.position("elsewhere"), None);
+    }
+
+    #[test]
+    fn helpers() {
+        let source = Source::new(INPUT);
+        let robot = |line: &'static str| -> Result<(i32, i32), ParseError> {
+            let (_, v) = source.split_once(line, " ")?;
+            let (x, y) = source.split_once(source.strip_prefix(v, "v=")?, ",")?;
+            Ok((source.parse(x)?, source.parse(y)?))
+        };
+        let mut lines = INPUT.lines();
+        assert_eq!(robot(lines.next().unwrap()), Ok((3, -3)));
+        assert_eq!(robot(lines.next().unwrap()), Ok((-1, -3)));
+        let err = robot(lines.next().unwrap()).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 13));
+        assert_eq!(
+            err.to_string(),
+            r#"line 3, column 13: found "y", expected i32"#
+        );
+
+        let err = source.strip_prefix(&INPUT[13..], "v=").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"line 2, column 1: found "p=6,3 v=-1,-3", expected "v=" first"#
+        );
+        let err = source.split_once(&INPUT[INPUT.len()..], ",").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"line 3, column 14: found nothing, expected "," somewhere"#
+        );
+    }
+
+    #[test]
+    fn from_grid() {
+        let err: ParseError = Grid::<u32>::parse("12\n3x").unwrap_err().into();
+        assert_eq!(
+            err.to_string(),
+            r#"line 2, column 2: found "x", expected a digit"#
+        );
+    }
+
+    #[cfg(feature = "nom")]
+    #[test]
+    fn nom_errors() {
+        use nom::{
+            branch::alt,
+            bytes::complete::tag,
+            character::complete::{alpha1, digit1},
+            combinator::map_res,
+            error::{context, VerboseError},
+            sequence::{preceded, separated_pair},
+            IResult,
+        };
+
+        fn cubes(line: &str) -> IResult<&str, (u32, &str)> {
+            let count = map_res(digit1, str::parse);
+            preceded(tag("Game 1: "), separated_pair(count, tag(" "), alpha1))(line)
+        }
+        fn count(line: &str) -> IResult<&str, &str, VerboseError<&str>> {
+            preceded(tag("Game 3: "), context("a count", digit1))(line)
+        }
+        fn colour(line: &str) -> IResult<&str, &str, VerboseError<&str>> {
+            let colour = alt((tag("red"), tag("green"), tag("blue")));
+            preceded(tag("Game 3: 1 "), context("a colour", colour))(line)
+        }
+
+        let input = "Game 1: 3 blue\nGame 2: x blue\nGame 3: 1 pink";
+        let source = Source::new(input);
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(source.nom(lines[0], cubes), Ok((3, "blue")));
+        assert_eq!(
+            source.nom(lines[1], cubes).unwrap_err().to_string(),
+            r#"line 2, column 1: found "Game 2: x blue", expected a different tag"#
+        );
+        assert_eq!(
+            source.nom(lines[2], count).unwrap_err().to_string(),
+            r#"line 3, column 11: found "pink", expected the end of the input"#
+        );
+        assert_eq!(
+            source.nom(lines[2], colour).unwrap_err().to_string(),
+            r#"line 3, column 11: found "pink", expected a colour"#
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::grid::Grid;
+
+/// An unbounded grid keyed by signed `(row, col)` positions.
+///
+/// The bounding box grows to cover every inserted position. It is not shrunk
+/// by [`SparseGrid::remove`]; call [`SparseGrid::recompute_bounds`] for that.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(isize, isize), T>,
+    bounds: Option<((isize, isize), (isize, isize))>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Inclusive `(min, max)` corners of the bounding box, or `None` if nothing
+    /// has been inserted.
+    #[inline]
+    pub fn bounds(&self) -> Option<((isize, isize), (isize, isize))> {
+        self.bounds
+    }
+
+    /// (height, width) of the bounding box.
+    pub fn dims(&self) -> (usize, usize) {
+        self.bounds.map_or((0, 0), |((r0, c0), (r1, c1))| {
+            ((r1 - r0) as usize + 1, (c1 - c0) as usize + 1)
+        })
+    }
+
+    /// Whether `pos` lies inside the bounding box, occupied or not.
+    pub fn in_bounds(&self, (r, c): (isize, isize)) -> bool {
+        self.bounds
+            .is_some_and(|((r0, c0), (r1, c1))| (r0..=r1).contains(&r) && (c0..=c1).contains(&c))
+    }
+
+    #[inline]
+    pub fn contains_key(&self, pos: (isize, isize)) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    #[inline]
+    pub fn get(&self, pos: (isize, isize)) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, pos: (isize, isize)) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Sets the cell at `pos`, returning the previous value.
+    pub fn insert(&mut self, pos: (isize, isize), value: T) -> Option<T> {
+        self.grow(pos);
+        self.cells.insert(pos, value)
+    }
+
+    /// Returns the cell at `pos`, inserting `f()` first if it is empty.
+    pub fn get_or_insert_with(&mut self, pos: (isize, isize), f: impl FnOnce() -> T) -> &mut T {
+        self.grow(pos);
+        self.cells.entry(pos).or_insert_with(f)
+    }
+
+    pub fn remove(&mut self, pos: (isize, isize)) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    /// Shrinks the bounding box to fit the occupied cells.
+    pub fn recompute_bounds(&mut self) {
+        self.bounds = None;
+        let positions: Vec<_> = self.cells.keys().copied().collect();
+        for pos in positions {
+            self.grow(pos);
+        }
+    }
+
+    fn grow(&mut self, (r, c): (isize, isize)) {
+        self.bounds = Some(match self.bounds {
+            None => ((r, c), (r, c)),
+            Some(((r0, c0), (r1, c1))) => ((r0.min(r), c0.min(c)), (r1.max(r), c1.max(c))),
+        });
+    }
+
+    /// Iterates over the occupied cells in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> {
+        self.cells.iter().map(|(&pos, c)| (pos, c))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Copies the bounding box into a dense grid, filling empty cells with
+    /// `fill`. The grid's `(0, 0)` is the bounding box's minimum corner.
+    pub fn to_grid(&self, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some(((r0, c0), _)) = self.bounds else {
+            return Grid::new(0, 0, fill);
+        };
+        let (height, width) = self.dims();
+        let mut grid = Grid::new(width, height, fill);
+        for (&(r, c), cell) in &self.cells {
+            grid[((r - r0) as usize, (c - c0) as usize)] = cell.clone();
+        }
+        grid
+    }
+
+    /// Renders the bounding box one row per line, drawing `fill` for empty
+    /// cells.
+    pub fn render(&self, fill: char) -> String
+    where
+        T: Display,
+    {
+        let mut out = String::new();
+        if let Some(((r0, c0), (r1, c1))) = self.bounds {
+            for r in r0..=r1 {
+                for c in c0..=c1 {
+                    match self.get((r, c)) {
+                        Some(cell) => out.push_str(&cell.to_string()),
+                        None => out.push(fill),
+                    }
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl<T> Grid<T> {
+    /// Collects the cells that satisfy `keep` into a [`SparseGrid`] with the
+    /// same coordinates.
+    pub fn to_sparse(&self, mut keep: impl FnMut(&T) -> bool) -> SparseGrid<T>
+    where
+        T: Clone,
+    {
+        self.enumerate()
+            .filter(|(_, c)| keep(c))
+            .map(|((r, c), cell)| ((r as isize, c as isize), cell.clone()))
+            .collect()
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        let width = grid.width();
+        grid.into_vec()
+            .into_iter()
+            .enumerate()
+            .map(|(i, cell)| (((i / width) as isize, (i % width) as isize), cell))
+            .collect()
+    }
+}
+
+impl<T> FromIterator<((isize, isize), T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = ((isize, isize), T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<((isize, isize), T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = ((isize, isize), T)>>(&mut self, iter: I) {
+        for (pos, cell) in iter {
+            self.insert(pos, cell);
+        }
+    }
+}
+
+/// Empty cells are drawn as `.`.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render('.'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.dims(), (0, 0));
+
+        grid.insert((0, 0), '#');
+        grid.insert((-2, 3), '#');
+        assert_eq!(grid.insert((0, 0), 'S'), Some('#'));
+        assert_eq!(grid.bounds(), Some(((-2, 0), (0, 3))));
+        assert_eq!(grid.dims(), (3, 4));
+        assert!(grid.in_bounds((-1, 1)));
+        assert!(!grid.contains_key((-1, 1)));
+        assert_eq!(grid.to_string(), "...#\n....\nS...\n");
+
+        grid.remove((-2, 3));
+        assert_eq!(grid.dims(), (3, 4));
+        grid.recompute_bounds();
+        assert_eq!(grid.bounds(), Some(((0, 0), (0, 0))));
+    }
+
+    #[test]
+    fn dense_round_trip() {
+        let dense = Grid::<char>::parse("..#\n#..").unwrap();
+        let sparse = dense.to_sparse(|&c| c == '#');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.get((1, 0)), Some(&'#'));
+        assert_eq!(sparse.render(' '), "  #\n#  \n");
+        assert_eq!(sparse.to_grid('.'), dense);
+        assert_eq!(SparseGrid::from(dense).len(), 6);
+    }
+}
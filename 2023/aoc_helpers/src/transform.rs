@@ -0,0 +1,253 @@
+use std::ops::Index;
+
+use crate::grid::Grid;
+
+impl<T> Grid<T> {
+    /// The cells of row `r`.
+    #[inline]
+    pub fn row(&self, r: usize) -> &[T] {
+        let start = r * self.width();
+        &self.as_slice()[start..start + self.width()]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        let (start, width) = (r * self.width(), self.width());
+        &mut self.as_mut_slice()[start..start + width]
+    }
+
+    /// Iterates over the rows from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        (0..self.height()).map(|r| self.row(r))
+    }
+
+    /// Iterates over the cells of column `c` from top to bottom.
+    pub fn column(&self, c: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(c < self.width(), "column {} out of bounds", c);
+        self.as_slice()[c..].iter().step_by(self.width())
+    }
+
+    /// Iterates over the columns from left to right.
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl Iterator<Item = &T>> + '_ {
+        (0..self.width()).map(|c| self.column(c))
+    }
+
+    /// Builds a `width` x `height` grid where each cell is copied from the
+    /// position `source` maps it to.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn((usize, usize)) -> (usize, usize),
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|r| (0..width).map(move |c| (r, c)))
+            .map(|pos| self[source(pos)].clone())
+            .collect();
+        Grid::from_vec(width, cells)
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height(), self.width(), |(r, c)| (c, r))
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_90(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let h = self.height();
+        self.remap(h, self.width(), |(r, c)| (h - 1 - c, r))
+    }
+
+    pub fn rotate_180(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut cells = self.as_slice().to_vec();
+        cells.reverse();
+        Grid::from_vec(self.width(), cells)
+    }
+
+    /// Rotates a quarter turn counter-clockwise.
+    pub fn rotate_270(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let w = self.width();
+        self.remap(self.height(), w, |(r, c)| (c, w - 1 - r))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        for r in 0..grid.height() {
+            grid.row_mut(r).reverse();
+        }
+        grid
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.rows().rev().flatten().cloned().collect();
+        Grid::from_vec(self.width(), cells)
+    }
+
+    /// Borrows the `height` x `width` rectangle whose top-left corner is `origin`.
+    ///
+    /// Panics if the rectangle doesn't fit inside the grid.
+    pub fn view(&self, origin: (usize, usize), (height, width): (usize, usize)) -> GridView<'_, T> {
+        assert!(
+            origin.0 + height <= self.height() && origin.1 + width <= self.width(),
+            "view of {}x{} at {:?} exceeds {}x{} grid",
+            height,
+            width,
+            origin,
+            self.height(),
+            self.width()
+        );
+        GridView {
+            grid: self,
+            origin,
+            width,
+            height,
+        }
+    }
+}
+
+/// A borrowed rectangular window into a [`Grid`], indexed relative to its
+/// own top-left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: (usize, usize),
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// (height, width)
+    #[inline]
+    pub fn dims(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Position of the view's top-left corner in the underlying grid.
+    #[inline]
+    pub fn origin(&self) -> (usize, usize) {
+        self.origin
+    }
+
+    #[inline]
+    pub fn contains(&self, (r, c): (usize, usize)) -> bool {
+        r < self.height && c < self.width
+    }
+
+    #[inline]
+    pub fn get(&self, pos: (usize, usize)) -> Option<&'a T> {
+        self.contains(pos)
+            .then(|| &self.grid[(self.origin.0 + pos.0, self.origin.1 + pos.1)])
+    }
+
+    /// The cells of row `r` of the view.
+    #[inline]
+    pub fn row(&self, r: usize) -> &'a [T] {
+        assert!(r < self.height, "row {} out of bounds", r);
+        &self.grid.row(self.origin.0 + r)[self.origin.1..self.origin.1 + self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'a [T]> + '_ {
+        (0..self.height).map(|r| self.row(r))
+    }
+
+    /// Copies the view into an owned grid.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_vec(self.width, self.rows().flatten().cloned().collect())
+    }
+}
+
+impl<T> Index<(usize, usize)> for GridView<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        assert!(self.contains((r, c)), "{:?} out of bounds", (r, c));
+        &self.grid[(self.origin.0 + r, self.origin.1 + c)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef").unwrap()
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = example();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = example();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_90().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_180().to_string(), "fed\ncba\n");
+        assert_eq!(grid.rotate_270().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+
+        assert_eq!(grid.rotate_90().rotate_90(), grid.rotate_180());
+        assert_eq!(grid.rotate_90().rotate_270(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn views() {
+        let grid = Grid::<char>::parse("abcd\nefgh\nijkl").unwrap();
+        let view = grid.view((1, 1), (2, 2));
+        assert_eq!(view.dims(), (2, 2));
+        assert_eq!(view[(0, 0)], 'f');
+        assert_eq!(view.get((1, 1)), Some(&'k'));
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.row(1), &['j', 'k']);
+        assert_eq!(view.to_grid().to_string(), "fg\njk\n");
+    }
+}
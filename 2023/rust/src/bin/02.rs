@@ -1,50 +1,68 @@
+use aoc_helpers::{ParseError, Source};
 use nom::{
-    bytes::complete::{tag, take_while},
+    branch::alt,
+    bytes::complete::tag,
     character::complete::digit1,
+    combinator::{cut, map_res},
+    error::{context, VerboseError},
     multi::separated_list1,
-    sequence::{terminated, tuple},
+    sequence::{delimited, separated_pair, tuple},
     IResult,
 };
 
-fn game_id(input: &str) -> IResult<&str, u32> {
-    tuple((tag("Game "), digit1, tag(": ")))(input)
-        .map(|(i, (_, id, _))| (i, id.parse::<u32>().unwrap()))
+fn game_id(input: &str) -> IResult<&str, u32, VerboseError<&str>> {
+    delimited(
+        context("\"Game \"", tag("Game ")),
+        context("a game number", map_res(digit1, str::parse)),
+        context("\": \"", tag(": ")),
+    )(input)
 }
 
-fn parse_round(input: &str) -> IResult<&str, Vec<(u8, &str)>> {
-    tuple((separated_list1(
+/// The cubes shown in one round, as counts and colours.
+type Round<'a> = Vec<(u8, &'a str)>;
+
+fn parse_round(input: &str) -> IResult<&str, Round<'_>, VerboseError<&str>> {
+    separated_list1(
         tag(", "),
-        nom::sequence::tuple((
-            terminated(digit1, tag(" ")),
-            take_while(|c: char| c.is_alphabetic()),
+        cut(separated_pair(
+            context("a number of cubes", map_res(digit1, str::parse)),
+            context("\" \"", tag(" ")),
+            context(
+                "red, green or blue",
+                alt((tag("red"), tag("green"), tag("blue"))),
+            ),
         )),
-    ),))(input)
-    .map(|(i, (v,))| {
-        let mut v2 = Vec::new();
-        for (a, b) in v {
-            v2.push((a.parse::<u8>().unwrap(), b));
-        }
-        (i, v2)
-    })
+    )(input)
 }
 
-fn parse_game(input: &str) -> IResult<&str, (u32, Vec<Vec<(u8, &str)>>)> {
+fn parse_game(input: &str) -> IResult<&str, (u32, Vec<Round<'_>>), VerboseError<&str>> {
     tuple((game_id, separated_list1(tag("; "), parse_round)))(input)
-        .map(|(i, (id, v))| (i, (id, v)))
 }
 
-#[aoc::main(02)]
-fn main(input: &str) -> (usize, usize) {
+#[aoc::main(
+    02,
+    example = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    expect = (8, 2286)
+)]
+fn main(input: &str) -> Result<(usize, usize), ParseError> {
     const RED_CUBES: u8 = 12;
     const GREEN_CUBES: u8 = 13;
     const BLUE_CUBES: u8 = 14;
 
-    /* part 1 */
-    let possible_games_id_sum: u32 = input
+    let source = Source::new(input);
+    let games = input
         .lines()
-        .filter_map(|line| {
-            let (_, (id, rounds)) = parse_game(line).unwrap();
+        .map(|line| source.nom(line, parse_game))
+        .collect::<Result<Vec<_>, _>>()?;
 
+    /* part 1 */
+    let possible_games_id_sum: u32 = games
+        .iter()
+        .filter_map(|(id, rounds)| {
             if rounds
                 .iter()
                 .flatten()
@@ -55,7 +73,7 @@ fn main(input: &str) -> (usize, usize) {
                     _ => unreachable!(),
                 })
             {
-                Some(id)
+                Some(*id)
             } else {
                 None
             }
@@ -63,11 +81,9 @@ fn main(input: &str) -> (usize, usize) {
         .sum();
 
     /* part 2 */
-    let part_two_sum: u64 = input
-        .lines()
-        .map(|line| {
-            let (_, (_id, rounds)) = parse_game(line).unwrap();
-
+    let part_two_sum: u64 = games
+        .iter()
+        .map(|(_id, rounds)| {
             let (max_red, max_green, max_blue) = rounds.iter().flatten().fold(
                 (0, 0, 0),
                 |(max_red, max_green, max_blue), &(count, cube_color)| match cube_color {
@@ -82,5 +98,5 @@ fn main(input: &str) -> (usize, usize) {
         })
         .sum();
 
-    (possible_games_id_sum as usize, part_two_sum as usize)
+    Ok((possible_games_id_sum as usize, part_two_sum as usize))
 }
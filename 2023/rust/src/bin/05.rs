@@ -1,10 +1,13 @@
 use std::collections::HashSet;
 
+use aoc_helpers::{ParseError, Source};
 use nom::{
-    bytes::complete::{tag, take_while1},
-    character::complete::{digit1, newline, space1},
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, newline, space1},
+    combinator::{cut, map_res, not},
+    error::{context, VerboseError},
     multi::separated_list1,
-    sequence::{terminated, tuple},
+    sequence::{pair, preceded, terminated, tuple},
     IResult,
 };
 
@@ -40,46 +43,48 @@ fn insert_range(ranges: &mut Vec<SeedRange>, new_range: SeedRange) {
     ranges.push(new_range);
 }
 
-fn parse_map_line(input: &str) -> IResult<&str, (usize, usize, usize)> {
+fn number(input: &str) -> IResult<&str, usize, VerboseError<&str>> {
+    context("a number", map_res(digit1, str::parse))(input)
+}
+
+fn parse_map_line(input: &str) -> IResult<&str, Mapping, VerboseError<&str>> {
     tuple((
-        terminated(digit1, space1),
-        terminated(digit1, space1),
-        digit1,
+        terminated(number, context("a space", space1)),
+        terminated(number, context("a space", space1)),
+        number,
     ))(input)
-    .map(|(next, res)| {
-        let res = (
-            res.0.parse::<usize>().unwrap(),
-            res.1.parse::<usize>().unwrap(),
-            res.2.parse::<usize>().unwrap(),
-        );
-
-        (next, res)
-    })
+    .map(|(next, (dest, src, range))| (next, Mapping(dest, src, range)))
+}
+
+fn parse_map(input: &str) -> IResult<&str, Vec<Mapping>, VerboseError<&str>> {
+    preceded(
+        context(
+            "\"<source>-to-<destination> map:\"",
+            tuple((alpha1, tag("-to-"), alpha1, tag(" map:\n"))),
+        ),
+        // A blank line ends the map, so any other line has to be a mapping.
+        cut(separated_list1(
+            pair(newline, not(newline)),
+            cut(parse_map_line),
+        )),
+    )(input)
 }
-fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
+
+fn parse_almanac(input: &str) -> IResult<&str, Almanac, VerboseError<&str>> {
     tuple((
-        tag("seeds: "),
-        separated_list1(space1, digit1),
-        terminated(take_while1(|c| c != ':'), tag(":\n")),
-        separated_list1(
-            newline,
-            tuple((
-                separated_list1(newline, parse_map_line),
-                terminated(take_while1(|c| c != ':'), tag(":")),
-            )),
+        preceded(
+            context("\"seeds: \"", tag("seeds: ")),
+            separated_list1(space1, cut(number)),
         ),
-        newline,
-        separated_list1(newline, parse_map_line),
+        context("a blank line", tag("\n\n")),
+        separated_list1(tag("\n\n"), cut(parse_map)),
     ))(input)
-    .map(|(next, (_, seeds, _, mappings, _, last_mapping))| {
+    .map(|(next, (seeds, _, mappings))| {
         let mut almanac = Almanac::default();
 
         let mut ranges = Vec::new();
         for seed_range in seeds.chunks(2) {
-            if let (Some(start_str), Some(range_str)) = (seed_range.get(0), seed_range.get(1)) {
-                let start = start_str.parse::<usize>().unwrap();
-                let range = range_str.parse::<usize>().unwrap();
-
+            if let &[start, range] = seed_range {
                 // for part 1
                 almanac.seeds.insert(start);
                 almanac.seeds.insert(range);
@@ -89,23 +94,7 @@ fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
         }
 
         almanac.range_of_seeds = ranges;
-
-        let mut entries = mappings.iter();
-        while let Some((mappings, _)) = entries.next() {
-            almanac.mappings.push(
-                mappings
-                    .iter()
-                    .map(|(dest, src, range)| Mapping(*dest, *src, *range))
-                    .collect(),
-            );
-        }
-
-        almanac.mappings.push(
-            last_mapping
-                .iter()
-                .map(|(humidity, location, range)| Mapping(*humidity, *location, *range))
-                .collect(),
-        );
+        almanac.mappings = mappings;
 
         (next, almanac)
     })
@@ -135,8 +124,8 @@ impl Almanac {
 }
 
 #[aoc::main(05)]
-fn main(input: &str) -> (usize, usize) {
-    let (_, almanac) = parse_almanac(&input).unwrap();
+fn main(input: &str) -> Result<(usize, usize), ParseError> {
+    let almanac = Source::new(input).nom(input, parse_almanac)?;
 
     let mappings = almanac.mappings;
 
@@ -202,5 +191,5 @@ fn main(input: &str) -> (usize, usize) {
 
     let p2 = current_ranges.iter().map(|x| x.0).min().unwrap();
 
-    (p1.1, p2)
+    Ok((p1.1, p2))
 }
@@ -20,7 +20,12 @@ fn parse_line_to_vec(line: Option<&str>) -> Vec<usize> {
         .collect() // Collect values into a Vec<usize>
 }
 
-#[aoc::main(06)]
+#[aoc::main(
+    06,
+    example = "Time:      7  15   30
+Distance:  9  40  200",
+    expect = (288, 71503)
+)]
 fn main(input: &str) -> (usize, usize) {
     let mut lines = input.lines();
     let times = parse_line_to_vec(lines.next());
@@ -18,7 +18,7 @@ impl HandStrength {
         });
 
         let mut counts = counts.iter().enumerate().collect::<Vec<_>>();
-        println!("{:?}", counts);
+        eprintln!("{:?}", counts);
         counts.sort_by(|a, b| b.1.cmp(a.1));
 
         let counts = counts.iter().map(|(_, v)| **v).collect::<Vec<_>>();
@@ -47,7 +47,7 @@ impl HandStrength {
     fn from_cards2(cards: &[Card]) -> HandStrength {
         let mut counts = [0; 15];
         let jokers = cards.iter().filter(|c| **c == Card::Joker).count();
-        println!("hand: {:?}\njokers: {}\n----", cards, jokers);
+        eprintln!("hand: {:?}\njokers: {}\n----", cards, jokers);
 
         for card in cards {
             if *card != Card::Joker {
@@ -191,8 +191,6 @@ pub fn part_two(hands: &mut [Hand]) -> usize {
 
 #[aoc::main(07)]
 fn main(input: &str) -> (usize, usize) {
-    //let input = std::fs::read_to_string("../inputs/07.test").unwrap();
-
     let hands = input
         .lines()
         .map(|line| {
@@ -225,8 +223,8 @@ fn main(input: &str) -> (usize, usize) {
 
     let total_winnings = part_two(&mut hands.to_vec());
 
-    println!("{:?}", ranked);
-    println!("winnings: {}", total_winnings);
+    eprintln!("{:?}", ranked);
+    eprintln!("winnings: {}", total_winnings);
 
     assert!(
         total_winnings != 251617933 || total_winnings != 251068434 || total_winnings > 251617933
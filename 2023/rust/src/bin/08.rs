@@ -54,16 +54,7 @@ fn build_tree(nodes: &[Node]) -> HashMap<String, Node> {
     tree
 }
 
-fn build_tree2<'a>(nodes: &'a [Node]) -> HashMap<&'a str, Node> {
-    let mut tree = HashMap::new();
-    for node in nodes {
-        tree.insert(node.ident.as_str(), node.clone());
-    }
-
-    tree
-}
-
-fn p1(input: &str) {
+fn p1(input: &str) -> usize {
     let (_, (directions, nodes)) = parse_node_map(&input).unwrap();
 
     let directions = directions.chars().collect::<Vec<_>>();
@@ -90,7 +81,7 @@ fn p1(input: &str) {
         }
     }
 
-    println!("Reached 'ZZZ' after {} steps", steps);
+    steps
 }
 
 fn find_cycle_length(
@@ -124,7 +115,7 @@ fn find_cycle_length(
     None // If path ends in 'Z' without forming a cycle
 }
 
-fn p2(input: &[u8]) {
+fn p2(input: &[u8]) -> usize {
     let split = input.iter().position(|&c| c == b'\n').unwrap();
 
     let (mut map, mut starts) = ([0u32; 0b11001_11001_11001 + 1], Vec::with_capacity(6));
@@ -138,55 +129,28 @@ fn p2(input: &[u8]) {
         }
     });
 
-    println!(
-        "{}",
-        starts
-            .into_iter()
-            .map(|node| {
-                input[0..split]
-                    .iter()
-                    .cycle()
-                    .scan(node, |node, step| {
-                        *node = if step == &b'L' {
-                            map[*node as usize] & u16::MAX as u32
-                        } else {
-                            map[*node as usize] >> 16
-                        };
-                        Some(*node & 0b11111 == (b'Z' - b'A') as u32)
-                    })
-                    .position(|node| node)
-                    .unwrap()
-                    + 1
-            })
-            .fold(1, lcm)
-    );
+    starts
+        .into_iter()
+        .map(|node| {
+            input[0..split]
+                .iter()
+                .cycle()
+                .scan(node, |node, step| {
+                    *node = if step == &b'L' {
+                        map[*node as usize] & u16::MAX as u32
+                    } else {
+                        map[*node as usize] >> 16
+                    };
+                    Some(*node & 0b11111 == (b'Z' - b'A') as u32)
+                })
+                .position(|node| node)
+                .unwrap()
+                + 1
+        })
+        .fold(1, lcm)
 }
 
 #[aoc::main(08)]
 fn main(input: &str) -> (usize, usize) {
-    //let input = std::fs::read_to_string("../inputs/08.test").unwrap();
-
-    let (_, (directions, nodes)) = parse_node_map(&input).unwrap();
-
-    let directions = directions.chars().collect::<Vec<_>>();
-
-    let tree = build_tree2(&nodes);
-
-    let mut steps = 0;
-
-    // Find all starting nodes (ending with 'A')
-    let mut current_nodes: Vec<&str> = tree
-        .keys()
-        .filter(|ident| ident.ends_with("A"))
-        .cloned()
-        .collect();
-
-    println!("starting nodes: {:?}", current_nodes);
-
-    let mut lcm_steps = 1;
-
-    let p2_solution = p2(input.as_bytes());
-    //println!("nodes: {:?}", current_nodes);
-
-    (0, 0)
+    (p1(input), p2(input.as_bytes()))
 }
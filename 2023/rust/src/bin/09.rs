@@ -28,17 +28,20 @@ fn parse_input(input: &str) -> Vec<Vec<i32>> {
     input.lines().map(parse_values).collect()
 }
 
-#[aoc::main(09)]
-fn main(input: &str) -> (usize, usize) {
+#[aoc::main(
+    09,
+    example = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45",
+    expect = (114, 2)
+)]
+fn main(input: &str) -> (i32, i32) {
     let parsed = parse_input(input);
 
     // Part 1.
-    println!("Part 1:");
     let sum_nexts: i32 = parsed.iter().map(|num| find_next_value(num)).sum();
-    println!("Sum (forward): {sum_nexts}");
 
     // Part 2.
-    println!("Part 2:");
     let reversed: Vec<Vec<i32>> = parsed
         .iter()
         .cloned()
@@ -48,7 +51,6 @@ fn main(input: &str) -> (usize, usize) {
         })
         .collect();
     let sum_prevs: i32 = reversed.iter().map(|num| find_next_value(num)).sum();
-    println!("Sum (backward): {sum_prevs}");
 
-    (0, 0)
+    (sum_nexts, sum_prevs)
 }
@@ -245,9 +245,6 @@ fn part_2(input: &str) -> i32 {
 }
 
 #[aoc::main(10)]
-fn main(input: &str) -> (usize, usize) {
-    println!("Part 1: {}", part_1(&input));
-    println!("Part 2: {}", part_2(&input));
-
-    (0, 0)
+fn main(input: &str) -> (i32, i32) {
+    (part_1(input), part_2(input))
 }
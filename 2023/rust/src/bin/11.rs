@@ -1,5 +1,7 @@
 use std::collections::BTreeMap;
 
+use aoc_helpers::Grid;
+
 fn parse_solar_system(input: &str) -> Vec<Vec<char>> {
     let mut solar_system = Vec::new();
 
@@ -184,16 +186,16 @@ mod tests {
                     galaxies.insert(galaxies.len() + 1, (i, j));
                 }
 
-                print!("{}", c);
+                eprint!("{}", c);
             }
 
-            println!();
+            eprintln!();
         }
 
         // find the total number of pairs of galaxies
         let pairs = galaxies.len() * (galaxies.len() - 1) / 2;
 
-        println!("pairs: {}", pairs);
+        eprintln!("pairs: {}", pairs);
 
         let start = galaxies.get(&5).unwrap();
         let end = galaxies.get(&9).unwrap();
@@ -207,7 +209,7 @@ mod tests {
         for p in test_pairs.iter() {
             let distance = (end.0 as isize - start.0 as isize).abs()
                 + (end.1 as isize - start.1 as isize).abs();
-            println!("distance: {}", distance);
+            eprintln!("distance: {}", distance);
         }
     }
 }
@@ -223,16 +225,16 @@ fn p1(input: &str) -> usize {
                 galaxies.insert(galaxies.len() + 1, (i, j));
             }
 
-            print!("{}", c);
+            eprint!("{}", c);
         }
 
-        println!();
+        eprintln!();
     }
 
     // find the total number of pairs of galaxies
     let pairs = galaxies.len() * (galaxies.len() - 1) / 2;
 
-    println!("pairs: {}", pairs);
+    eprintln!("pairs: {}", pairs);
 
     // for each of the pairs, find the shortest path between the galaxies
     let mut total_sum = 0;
@@ -240,13 +242,13 @@ fn p1(input: &str) -> usize {
         for (j, end) in galaxies.range((i + 1)..) {
             let distance = (end.0 as isize - start.0 as isize).abs()
                 + (end.1 as isize - start.1 as isize).abs();
-            println!("distance: {}", distance);
+            eprintln!("distance: {}", distance);
 
             total_sum += distance as usize;
         }
     }
 
-    println!("total sum: {}", total_sum);
+    eprintln!("total sum: {}", total_sum);
 
     total_sum
 }
@@ -271,48 +273,65 @@ fn calculate_distance(
     row_distance + col_distance
 }
 
-const GRID_SIZE: usize = 140;
-
-#[aoc::main(11)]
-fn main(input: &str) -> (usize, usize) {
-    let input = input.trim(); // println!("{input}");
-
-    // Count the nodes in each row, column, and in total.
-    let mut rows = [0; GRID_SIZE];
-    let mut cols = [0; GRID_SIZE];
-    let mut count = 0;
-    for (row, line) in input.lines().enumerate() {
-        for (col, char) in line.chars().enumerate() {
-            if char == '#' {
-                // println!("Galaxy at {row} {col}");
-                rows[row] += 1;
-                cols[col] += 1;
-                count += 1;
-            }
-        }
+/// Sums the shortest distances between every pair of galaxies, with each
+/// empty row and column `scale` times as wide.
+fn galaxy_distances(input: &str, scale: usize) -> usize {
+    let galaxies = Grid::<char>::parse(input.trim())
+        .unwrap()
+        .to_sparse(|&c| c == '#');
+
+    // Count the galaxies in each row and column of the bounding box.
+    let (height, width) = galaxies.dims();
+    let (min_row, min_col) = galaxies.bounds().map_or((0, 0), |(min, _)| min);
+    let mut rows = vec![0; height];
+    let mut cols = vec![0; width];
+    for (row, col) in galaxies.positions() {
+        rows[(row - min_row) as usize] += 1;
+        cols[(col - min_col) as usize] += 1;
     }
-    // println!("rows: #{rows:?}");
-    // println!("cols: #{cols:?}");
-
-    println!("Part 1: {}", calculate(&rows, &cols, count, 2));
-    println!("Part 2: {}", calculate(&rows, &cols, count, 1_000_000));
 
-    (0, 0)
+    calculate(&rows, galaxies.len(), scale) + calculate(&cols, galaxies.len(), scale)
 }
 
-fn calculate(
-    rows: &[usize; GRID_SIZE],
-    cols: &[usize; GRID_SIZE],
-    count: usize,
-    scale: usize,
-) -> usize {
+/// Sums the distances along one axis, where `counts[i]` is the number of
+/// galaxies in line `i`.
+fn calculate(counts: &[usize], count: usize, scale: usize) -> usize {
     let mut total = 0;
-    let mut running_count = (0, 0);
-    for i in 0..GRID_SIZE {
-        running_count.0 += rows[i];
-        running_count.1 += cols[i];
-        total += running_count.0 * (count - running_count.0) * if rows[i] == 0 { scale } else { 1 };
-        total += running_count.1 * (count - running_count.1) * if cols[i] == 0 { scale } else { 1 };
+    let mut running_count = 0;
+    for &n in counts {
+        running_count += n;
+        total += running_count * (count - running_count) * if n == 0 { scale } else { 1 };
     }
     total
 }
+
+#[aoc::main(11)]
+fn main(input: &str) -> (usize, usize) {
+    (
+        galaxy_distances(input, 2),
+        galaxy_distances(input, 1_000_000),
+    )
+}
+
+#[cfg(test)]
+mod example {
+    use super::*;
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn expansion() {
+        assert_eq!(galaxy_distances(EXAMPLE, 2), 374);
+        assert_eq!(galaxy_distances(EXAMPLE, 10), 1030);
+        assert_eq!(galaxy_distances(EXAMPLE, 100), 8410);
+    }
+}
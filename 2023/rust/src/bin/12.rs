@@ -72,7 +72,7 @@ fn rec(
     }
 }
 
-pub fn part1(input: &str) {
+pub fn part1(input: &str) -> i64 {
     let mut total: i64 = 0;
 
     for line in input.lines() {
@@ -91,10 +91,10 @@ pub fn part1(input: &str) {
         let matches = rec(&masked, 0, &nums, vec![], space, HashMap::new()).0;
         total += matches;
     }
-    println!("{}", total)
+    total
 }
 
-pub fn part2(input: &str) {
+pub fn part2(input: &str) -> i64 {
     let mut total: i64 = 0;
 
     for line in input.lines() {
@@ -126,13 +126,10 @@ pub fn part2(input: &str) {
         let space = masked.len() + 1 - sum - nums.len();
         total += rec(&masked, 0, &nums, vec![], space, HashMap::new()).0;
     }
-    println!("{}", total)
+    total
 }
 
 #[aoc::main(12)]
-fn main(input: &str) -> (usize, usize) {
-    part1(input);
-    part2(input);
-
-    (0, 0)
+fn main(input: &str) -> (i64, i64) {
+    (part1(input), part2(input))
 }
@@ -1,129 +1,4 @@
-use std::collections::btree_set::SymmetricDifference;
-
-fn is_vertical_symmetry(grid: &[Vec<char>], start_col: usize, end_col: usize) -> bool {
-    for row in grid {
-        if row[start_col] != row[end_col] {
-            return false;
-        }
-    }
-    true
-}
-
-fn is_horizontal_symmetry(grid: &[Vec<char>], top_row: usize, bottom_row: usize) -> bool {
-    for col in 0..grid[0].len() {
-        if grid[top_row][col] != grid[bottom_row][col] {
-            return false;
-        }
-    }
-    true
-}
-
-fn find_reflection_point(grid: &[Vec<char>]) -> usize {
-    // Check for vertical symmetry
-    let num_cols = grid[0].len();
-    let num_rows = grid.len();
-
-    for mid_col in 0..num_cols - 1 {
-        if is_vertical_symmetry(grid, mid_col, mid_col + 1) {
-            let mut left_col = mid_col;
-            let mut right_col = mid_col + 1;
-
-            println!(
-                "vertical symmetry between columns {} and {}",
-                left_col, right_col
-            );
-
-            while left_col > 0
-                && right_col < num_cols - 1
-                && is_vertical_symmetry(grid, left_col - 1, right_col + 1)
-            {
-                left_col -= 1;
-                right_col += 1;
-            }
-
-            println!("left_col: {}, right_col: {}", left_col, right_col);
-
-            // Ensure the reflection spans at least two columns
-            if right_col - left_col > 1 {
-                println!(
-                    " reflection between columns {} and {} | {} {}",
-                    left_col,
-                    right_col,
-                    mid_col + 1,
-                    mid_col + 2
-                );
-                // factor is the total number of columns to the left of the reflection point
-                let factor = mid_col + 1;
-                return factor;
-
-                // edge case: if the reflection point is at the left of the grid, return 1
-            } else if right_col - left_col == 1 {
-                if left_col == 0 && right_col == 1 {
-                    println!("[first] num_cols: {}", num_cols - 1);
-                    return 1;
-                } else if right_col == num_cols - 1 {
-                    println!("[last] num_cols: {}", num_cols - 1);
-                    return mid_col + 1;
-                }
-            }
-        }
-    }
-
-    for mid_row in 0..num_rows - 1 {
-        if is_horizontal_symmetry(grid, mid_row, mid_row + 1) {
-            let mut top_row = mid_row;
-            let mut bottom_row = mid_row + 1;
-
-            println!(
-                "horizontal symmetry between rows {} and {}",
-                top_row, bottom_row
-            );
-
-            while top_row > 0
-                && bottom_row < num_rows - 1
-                && is_horizontal_symmetry(grid, top_row - 1, bottom_row + 1)
-            {
-                top_row -= 1;
-                bottom_row += 1;
-            }
-
-            println!(
-                " reflection between rows {} and {} | {} {}\n---",
-                top_row,
-                bottom_row,
-                mid_row + 1,
-                mid_row + 2
-            );
-
-            // Ensure the reflection spans at least two rows
-            if bottom_row - top_row > 1 {
-                println!(
-                    "[clean] reflection between rows {} and {} | {} {}\n---",
-                    top_row,
-                    bottom_row,
-                    mid_row + 1,
-                    mid_row + 2
-                );
-                // factor is the total number of rows above the reflection point * 100
-                let factor = (mid_row + 1) * 100;
-                return factor;
-
-                // edge case: if the reflection point is at the top of the grid, return 100
-            } else if bottom_row - top_row == 1 {
-                if top_row == 0 && bottom_row == 1 {
-                    return 100;
-                } else if bottom_row == num_rows - 1 {
-                    println!(" num_rows: {}", num_rows - 1);
-                    return (mid_row + 1) * 100;
-                }
-            }
-        }
-    }
-
-    println!("---");
-
-    panic!("No reflection found");
-}
+use aoc_helpers::Grid;
 
 mod part_one {
     use super::*;
@@ -161,8 +36,7 @@ mod part_two {
 type Index = usize;
 type AB = (Index, Index);
 
-type Row = Vec<Cell>;
-type Group = Vec<Row>;
+type Group = Grid<Cell>;
 type Input = Vec<Group>;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -350,34 +224,28 @@ fn move_away_indeces((a, b): AB, c: usize, upper_limit: usize) -> (Option<usize>
 }
 
 fn width(group: &Group) -> usize {
-    group[0].len()
+    group.width()
 }
 
 fn height(group: &Group) -> usize {
-    group.len()
+    group.height()
 }
 
 fn get_column(group: &Group, col: usize) -> Vec<Cell> {
-    group.into_iter().map(|row| row[col]).collect()
+    group.column(col).copied().collect()
 }
 
 fn get_row(group: &Group, row: usize) -> Vec<Cell> {
-    group[row].clone()
+    group.row(row).to_vec()
 }
 
 fn parse_group(value: &str) -> Group {
-    value
-        .split("\n")
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => Cell::Rock,
-                    '.' => Cell::Ash,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect()
+    Grid::parse_with(value, |c| match c {
+        '#' => Some(Cell::Rock),
+        '.' => Some(Cell::Ash),
+        _ => None,
+    })
+    .unwrap()
 }
 
 fn parse_input() -> Input {
@@ -462,31 +330,10 @@ mod tests {
 
 #[aoc::main(13)]
 fn main(input: &str) -> (usize, usize) {
-    //let input = std::fs::read_to_string("../inputs/13.test").unwrap();
-    let patterns = input.split("\n\n").collect::<Vec<_>>();
-    let mut total = 0;
-    for p in &patterns {
-        println!("{}", p);
-        let grid_len = p.lines().count();
-        let cols = p.lines().next().unwrap().len();
-        let mut grid = vec![vec![' '; cols]; grid_len];
-        for (i, line) in p.lines().enumerate() {
-            for (j, c) in line.chars().enumerate() {
-                grid[i][j] = c;
-            }
-        }
-
-        let factor = find_reflection_point(&grid);
-        println!("Factor: {}", factor);
-        total += factor;
-    }
+    let groups = input.split("\n\n").map(parse_group).collect::<Vec<_>>();
 
-    println!("Total: {}", total);
+    let p1 = groups.iter().cloned().map(calc_group).sum();
+    let p2 = groups.into_iter().map(calc_group_smudged).sum();
 
-    let real = parse_input()
-        .into_iter()
-        .map(calc_group_smudged)
-        .sum::<usize>();
-    println!("Real: {}", real);
-    (0, 0)
+    (p1, p2)
 }
@@ -1,183 +1,109 @@
-#[derive(Debug, PartialEq, Eq)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Position {
-    row: usize,
-    col: usize,
-}
-
-impl Direction {
-    fn shift(&self, grid: &[Vec<u8>], pos: &Position) -> Position {
-        let Position { row, col } = *pos;
-        match self {
-            Direction::North => {
-                let mut irow = pos.row;
-
-                while irow > 0 && grid[irow - 1][col] == b'.' {
-                    irow -= 1;
-                }
-
-                Position { row: irow, col }
-            }
-            Direction::West => {
-                let mut icol = pos.col;
-                while icol > 0 && grid[row][icol - 1] == b'.' {
-                    icol -= 1;
-                }
-                Position { row, col: icol }
-            }
-            Direction::South => {
-                let mut irow = pos.row;
-                while irow < grid.len() - 1 && grid[irow + 1][col] == b'.' {
-                    irow += 1;
-                }
-                Position { row: irow, col }
-            }
-            Direction::East => {
-                let mut icol = pos.col;
-
-                while icol < grid[row].len() - 1 && grid[row][icol + 1] == b'.' {
-                    icol += 1;
+use aoc_helpers::Grid;
+use hashbrown::HashMap;
+
+/// Rolls every round rock as far north as it can go.
+///
+/// The other directions are handled by rotating the platform first.
+fn tilt_north(grid: &mut Grid<u8>) {
+    for col in 0..grid.width() {
+        let mut free_row = 0;
+        for row in 0..grid.height() {
+            match grid[(row, col)] {
+                b'#' => free_row = row + 1,
+                b'O' => {
+                    if free_row != row {
+                        grid[(free_row, col)] = b'O';
+                        grid[(row, col)] = b'.';
+                    }
+                    free_row += 1;
                 }
-                Position { row, col: icol }
+                _ => {}
             }
         }
     }
 }
 
-fn tilt(d: &Direction, tilted: &mut [Vec<u8>], pos: Position) {
-    if tilted[pos.row][pos.col] == b'O' {
-        let shifted_pos = d.shift(&tilted, &pos);
-        tilted[shifted_pos.row][shifted_pos.col] = b'O';
-        if shifted_pos != pos {
-            tilted[pos.row][pos.col] = b'.';
-        }
+/// Tilts north, west, south and east, in that order.
+fn spin_cycle(mut grid: Grid<u8>) -> Grid<u8> {
+    for _ in 0..4 {
+        tilt_north(&mut grid);
+        // rotating clockwise brings the west edge to the top
+        grid = grid.rotate_90();
     }
+    grid
 }
 
-fn simulate_cycle(mut tilted: &mut [Vec<u8>]) {
-    const DIRECTIONS: [Direction; 4] = [
-        Direction::North,
-        Direction::West,
-        Direction::South,
-        Direction::East,
-    ];
-    for d in DIRECTIONS.iter() {
-        match d {
-            Direction::East => {
-                for row in 0..tilted.len() {
-                    for col in (0..tilted[row].len()).rev() {
-                        tilt(&d, tilted, Position { row, col });
-                    }
-                }
-            }
-            Direction::South => {
-                for row in (0..tilted.len()).rev() {
-                    for col in 0..tilted[row].len() {
-                        tilt(&d, &mut tilted, Position { row, col });
-                    }
-                }
-            }
-            _ => {
-                (0..tilted.len()).for_each(|row| {
-                    (0..tilted[row].len()).for_each(|col| {
-                        tilt(&d, &mut tilted, Position { row, col });
-                    })
-                });
-            }
+fn cycle(mut grid: Grid<u8>, cycles: usize) -> Grid<u8> {
+    let mut seen: HashMap<Grid<u8>, usize> = HashMap::new();
+    let mut history = Vec::new();
+
+    for i in 0..cycles {
+        if let Some(&first) = seen.get(&grid) {
+            let cycle_length = i - first;
+            let remaining_cycles = (cycles - first) % cycle_length;
+            return history.swap_remove(first + remaining_cycles);
         }
+        seen.insert(grid.clone(), i);
+        history.push(grid.clone());
+        grid = spin_cycle(grid);
     }
 
-    //tilted
+    grid
 }
 
-fn cycle(mut grid: &mut [Vec<u8>], cycles: usize) {
-    let mut seen: Vec<Vec<Vec<u8>>> = Vec::new();
-    loop {
-        simulate_cycle(&mut grid);
-        if let Some(idx) = seen.iter().position(|g| g == &grid) {
-            let first_cycle_of_pattern = idx + 1;
-            let cycle_length = (seen.len() + 1) - first_cycle_of_pattern;
-            let remaining_cycles = (cycles - first_cycle_of_pattern) % cycle_length;
-
-            let res = &seen[idx + remaining_cycles];
-
-            grid.as_mut()
-                .iter_mut()
-                .zip(res.iter())
-                .for_each(|(row, x)| {
-                    row.iter_mut().zip(x.iter()).for_each(|(c, x)| {
-                        *c = *x;
-                    })
-                });
-
-            return;
-        }
-        seen.push(grid.to_owned());
-    }
+fn north_load(grid: &Grid<u8>) -> usize {
+    grid.rows()
+        .enumerate()
+        .map(|(row, cells)| {
+            let rocks = cells.iter().filter(|&&c| c == b'O').count();
+            rocks * (grid.height() - row)
+        })
+        .sum()
 }
 
-fn p1(lines: &Vec<Vec<u8>>) -> usize {
-    let mut tilted = vec![vec![b'.'; lines[0].len()]; lines.len()];
-    for row in 0..lines.len() {
-        for col in 0..lines[row].len() {
-            let symbol = lines[row][col];
-            if symbol != b'O' {
-                tilted[row][col] = symbol;
-                continue;
-            }
-            let mut irow = row;
-            while irow > 0 && tilted[irow - 1][col] == b'.' {
-                irow -= 1;
-            }
-            tilted[irow][col] = b'O';
-        }
-    }
-
-    let mut load_m = tilted.len();
-    let sum = tilted
-        .iter()
-        .map(|row| {
-            let rocks = row.iter().filter(|&&c| c == b'O').count();
-            let res = rocks * load_m;
-            load_m -= 1;
-            res
-        })
-        .sum::<usize>();
-    sum
+fn p1(grid: &Grid<u8>) -> usize {
+    let mut tilted = grid.clone();
+    tilt_north(&mut tilted);
+    north_load(&tilted)
 }
 
-fn p2(mut grid: &mut [Vec<u8>]) -> usize {
-    cycle(&mut grid, 1_000_000_000);
-    let mut load_m = grid.len();
-    let sum = grid
-        .iter()
-        .map(|row| {
-            let rocks = row.iter().filter(|&&c| c == b'O').count();
-            let res = rocks * load_m;
-            load_m -= 1;
-            res
-        })
-        .sum::<usize>();
-    sum
+fn p2(grid: &Grid<u8>) -> usize {
+    north_load(&cycle(grid.clone(), 1_000_000_000))
 }
 
 #[aoc::main(14)]
 fn main(input: &str) -> (usize, usize) {
-    let mut lines = input
-        .lines()
-        .map(|row| row.chars().map(|c| c as u8).collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+    let grid = Grid::<u8>::parse(input).unwrap();
 
-    let p1 = p1(&lines);
-    let p2 = p2(&mut lines);
+    let p1 = p1(&grid);
+    let p2 = p2(&grid);
 
     assert!(p2 == 93102 || p2 == 64);
     (p1, p2)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r"O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn test_p1() {
+        assert_eq!(p1(&Grid::parse(EXAMPLE).unwrap()), 136);
+    }
+
+    #[test]
+    fn test_p2() {
+        assert_eq!(p2(&Grid::parse(EXAMPLE).unwrap()), 64);
+    }
+}
@@ -1,40 +1,34 @@
+use aoc_helpers::Direction;
 use std::{
     cmp::Ordering,
     collections::{HashSet, VecDeque},
 };
 
-#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    #[default]
-    Right,
-}
-
-#[derive(Default, Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug)]
 struct LightBeam {
     direction: Direction,
     position: (usize, usize),
 }
 
+impl Default for LightBeam {
+    fn default() -> Self {
+        Self {
+            direction: Direction::Right,
+            position: (0, 0),
+        }
+    }
+}
+
 impl std::ops::AddAssign<usize> for LightBeam {
     fn add_assign(&mut self, rhs: usize) {
         let (row, col) = self.position;
-        match self.direction {
-            Direction::Right => {
-                self.position = (row, col + rhs);
-            }
-            Direction::Left => {
-                self.position = (row, col - rhs);
-            }
-            Direction::Up => {
-                self.position = (row - rhs, col);
-            }
-            Direction::Down => {
-                self.position = (row + rhs, col);
-            }
-        }
+        let (dr, dc) = self.direction.delta();
+        // stepping off the top/left wraps around to a huge index, which is
+        // then caught by the `>= grid` bounds comparison
+        self.position = (
+            (row as isize + dr * rhs as isize) as usize,
+            (col as isize + dc * rhs as isize) as usize,
+        );
     }
 }
 
@@ -42,21 +36,7 @@ impl std::ops::Add<usize> for LightBeam {
     type Output = Self;
 
     fn add(mut self, rhs: usize) -> Self::Output {
-        let (row, col) = self.position;
-        match self.direction {
-            Direction::Right => {
-                self.position = (row, col + rhs);
-            }
-            Direction::Left => {
-                self.position = (row, col - rhs);
-            }
-            Direction::Up => {
-                self.position = (row - rhs, col);
-            }
-            Direction::Down => {
-                self.position = (row + rhs, col);
-            }
-        }
+        self += rhs;
         self
     }
 }
@@ -122,7 +102,7 @@ impl LightBeam {
                         Direction::Right => Direction::Down,
                     };
                 }
-                b'|' if *direction == Direction::Left || *direction == Direction::Right => {
+                b'|' if direction.is_horizontal() => {
                     beam.direction = Direction::Down;
                     if beam + 1 < grid {
                         let mut child = LightBeam {
@@ -133,7 +113,7 @@ impl LightBeam {
                         queue.push_back(child);
                     }
                 }
-                b'-' if *direction == Direction::Up || *direction == Direction::Down => {
+                b'-' if direction.is_vertical() => {
                     beam.direction = Direction::Right;
                     if beam + 1 < grid {
                         let mut child = LightBeam {
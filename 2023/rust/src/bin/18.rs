@@ -60,14 +60,7 @@ fn part_2(input: &[u8]) -> u64 {
 }
 
 #[aoc::main(18)]
-fn main(input: &str) -> (usize, usize) {
-    //let input = std::fs::read_to_string("../inputs/18.test").unwrap();
+fn main(input: &str) -> (u32, u64) {
     let input = input.as_bytes();
-    let p1 = part_1(input);
-    let p2 = part_2(input);
-
-    println!("Part 1: {}", p1);
-    println!("Part 2: {}", p2);
-
-    (0, 0)
+    (part_1(input), part_2(input))
 }
@@ -1,12 +1,14 @@
 use std::{cmp::Ordering, collections::HashMap};
 
+use aoc_helpers::{ParseError, Source};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alpha1, digit1, newline},
-    combinator::opt,
-    multi::separated_list1,
-    sequence::{delimited, terminated, tuple},
+    combinator::{cut, map_res},
+    error::{context, VerboseError},
+    multi::{many0, separated_list1},
+    sequence::{delimited, preceded, terminated, tuple},
     IResult, Parser,
 };
 
@@ -48,86 +50,87 @@ struct MinMax {
     max: usize,
 }
 
-fn parse_rule(input: &str) -> IResult<&str, (&str, Option<(Ordering, &str, &str)>)> {
+fn number(input: &str) -> IResult<&str, usize, VerboseError<&str>> {
+    context("a number", map_res(digit1, str::parse))(input)
+}
+
+fn parse_rule(input: &str) -> IResult<&str, Rule, VerboseError<&str>> {
     tuple((
         alpha1,
-        opt(tuple((
-            alt((
-                tag("<").map(|_| Ordering::Less),
-                tag(">").map(|_| Ordering::Greater),
-            )),
-            terminated(digit1, tag(":")),
-            alpha1,
-        ))),
-    ))(input)
-    .map(|(input, res)| (input, res))
+        alt((
+            tag("<").map(|_| Ordering::Less),
+            tag(">").map(|_| Ordering::Greater),
+        )),
+        cut(terminated(number, context("\":\"", tag(":")))),
+        cut(context("a workflow name", alpha1)),
+    ))
+    .map(|(category, ordering, value, next)| Rule {
+        category: category.to_string(),
+        condition: Condition(ordering, value),
+        next: WorkflowId(next.to_string()),
+    })
+    .parse(input)
 }
 
-fn parse_workflows(input: &str) -> IResult<&str, HashMap<WorkflowId, Workflow>> {
+/// Every workflow, by name.
+type Workflows = HashMap<WorkflowId, Workflow>;
+
+fn parse_workflows(input: &str) -> IResult<&str, Workflows, VerboseError<&str>> {
     separated_list1(
         tag("\n"),
         tuple((
-            alpha1,
-            delimited(tag("{"), separated_list1(tag(","), parse_rule), tag("}")),
+            context("a workflow name", alpha1),
+            delimited(
+                context("\"{\"", tag("{")),
+                cut(tuple((
+                    many0(terminated(parse_rule, tag(","))),
+                    context("a rule or workflow name", alpha1),
+                ))),
+                cut(context("\"}\"", tag("}"))),
+            ),
         )),
-    )(input)
-    .map(|(input, res)| {
-        let mut workflows = HashMap::new();
-
-        for (workflow_id, mut rules) in res {
-            let last = rules.remove(rules.len() - 1).0;
-
-            let mut workflow = Workflow {
-                rules: vec![],
-                result: WorkflowId(last.to_string()),
-            };
-
-            for rule in rules {
-                let ident = rule.0;
-                let (condition, value, next_step) = rule.1.unwrap();
-                let condition = Condition(condition, value.parse::<usize>().unwrap());
-                workflow.rules.push(Rule {
-                    category: ident.to_string(),
-                    condition,
-                    next: WorkflowId(next_step.to_string()),
-                });
-            }
-
-            workflows.insert(WorkflowId(workflow_id.to_string()), workflow);
-        }
-
-        (input, workflows)
+    )
+    .map(|workflows| {
+        workflows
+            .into_iter()
+            .map(|(workflow_id, (rules, last))| {
+                let workflow = Workflow {
+                    rules,
+                    result: WorkflowId(last.to_string()),
+                };
+                (WorkflowId(workflow_id.to_string()), workflow)
+            })
+            .collect()
     })
+    .parse(input)
 }
 
-fn parse_parts(input: &str) -> IResult<&str, Vec<Part>> {
+fn parse_parts(input: &str) -> IResult<&str, Vec<Part>, VerboseError<&str>> {
     separated_list1(
         tag("\n"),
-        delimited(
-            tag("{"),
-            separated_list1(tag(","), tuple((alpha1, tag("="), digit1))),
-            tag("}"),
-        ),
+        tuple((
+            preceded(context("\"{x=\"", tag("{x=")), cut(number)),
+            cut(preceded(context("\",m=\"", tag(",m=")), number)),
+            cut(preceded(context("\",a=\"", tag(",a=")), number)),
+            cut(delimited(
+                context("\",s=\"", tag(",s=")),
+                number,
+                context("\"}\"", tag("}")),
+            )),
+        ))
+        .map(|(x, m, a, s)| Part { x, m, a, s }),
     )(input)
-    .map(|(input, res)| {
-        let mut parts = vec![];
-        for part in res {
-            let x = part[0].2.parse::<usize>().unwrap();
-            let m = part[1].2.parse::<usize>().unwrap();
-            let a = part[2].2.parse::<usize>().unwrap();
-            let s = part[3].2.parse::<usize>().unwrap();
-            parts.push(Part { x, m, a, s });
-        }
-
-        (input, parts)
-    })
 }
 
 fn parse_workflows_and_parts(
     input: &str,
-) -> IResult<&str, (HashMap<WorkflowId, Workflow>, Vec<Part>)> {
-    tuple((parse_workflows, newline, newline, parse_parts))(input)
-        .map(|(input, (workflows, _, _, parts))| (input, (workflows, parts)))
+) -> IResult<&str, (Workflows, Vec<Part>), VerboseError<&str>> {
+    tuple((
+        parse_workflows,
+        context("a blank line", tuple((newline, newline))),
+        parse_parts,
+    ))(input)
+    .map(|(input, (workflows, _, parts))| (input, (workflows, parts)))
 }
 
 fn process_workflow(
@@ -239,9 +242,9 @@ fn p2(workflows: &HashMap<WorkflowId, Workflow>) -> usize {
 }
 
 #[aoc::main(19)]
-fn main(input: &str) -> (usize, usize) {
-    let (_, (workflows, parts)) = parse_workflows_and_parts(&input).unwrap();
+fn main(input: &str) -> Result<(usize, usize), ParseError> {
+    let (workflows, parts) = Source::new(input).nom(input, parse_workflows_and_parts)?;
     let p1 = p1(&workflows, &parts);
     let p2 = p2(&workflows);
-    (p1, p2)
+    Ok((p1, p2))
 }
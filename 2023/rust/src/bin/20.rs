@@ -1,11 +1,13 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_helpers::{ParseError, Source};
 use hashbrown::HashSet;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alpha1, newline},
-    combinator::opt,
+    combinator::{cut, opt},
+    error::{context, VerboseError},
     multi::separated_list1,
     sequence::tuple,
     IResult,
@@ -66,15 +68,16 @@ impl Module {
     }
 }
 
-fn parse_config(input: &str) -> IResult<&str, HashMap<String, Module>> {
+fn parse_config(input: &str) -> IResult<&str, HashMap<String, Module>, VerboseError<&str>> {
+    let name = || context("a module name", alpha1);
     separated_list1(
         newline,
-        tuple((
+        cut(tuple((
             opt(alt((tag("%"), tag("&")))),
-            alpha1,
-            tag(" -> "),
-            separated_list1(tag(", "), alpha1),
-        )),
+            name(),
+            context("\" -> \"", tag(" -> ")),
+            separated_list1(tag(", "), cut(name())),
+        ))),
     )(input)
     .map(|(input, res)| {
         let mut modules = HashMap::new();
@@ -184,8 +187,8 @@ fn p1(modules: &mut HashMap<String, Module>) -> usize {
 }
 
 #[aoc::main(20)]
-fn main(input: &str) -> (usize, usize) {
-    let (_, mut modules) = parse_config(&input).unwrap();
+fn main(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut modules = Source::new(input).nom(input, parse_config)?;
     // get a list of all conjunction modules
     let conjunction_modules = modules
         .iter()
@@ -223,5 +226,5 @@ fn main(input: &str) -> (usize, usize) {
         }
     }
 
-    (p1(&mut modules), p2(&mut modules))
+    Ok((p1(&mut modules), p2(&mut modules)))
 }
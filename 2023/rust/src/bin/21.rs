@@ -1,95 +1,97 @@
+use aoc_helpers::Grid;
 use hashbrown::HashSet;
-use num::Integer;
+use std::collections::VecDeque;
 
-#[aoc::main(21)]
-pub fn main(input: &str) -> (usize, usize) {
-    let mut input = std::fs::read_to_string("../inputs/21.test").unwrap();
-    let grid = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let start = grid
-        .iter()
-        .enumerate()
-        .find(|(_, row)| row.iter().position(|&c| c == 'S').is_some())
-        .map(|(y, row)| (y, row.iter().position(|&c| c == 'S').unwrap()))
-        .unwrap();
-
-    println!("{:?}", grid[5][5]);
-
-    let mut visited = vec![vec![false; grid[0].len()]; grid.len()];
-    let mut plots_reached = HashSet::new();
-    let mut steps = 0;
-
-    let mut queue = vec![start];
-    const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    while steps != 6 {
-        if let Some((y, x)) = queue.pop() {
-            if visited[y][x] {
-                continue;
-            }
-            visited[y][x] = true;
+const P1_STEPS: usize = 64;
+const P2_STEPS: usize = 26501365;
 
-            if grid[y][x] == 'E' {
-                println!("found exit at {}, {}", y, x);
-                break;
-            }
+fn find_start(grid: &Grid<char>) -> (usize, usize) {
+    grid.find_unique(&'S').unwrap()
+}
 
-            for d in DIRECTIONS.iter() {
-                let (dr, dc) = d;
-                let (nr, nc) = (y as isize + dr, x as isize + dc);
-                if nr < 0 || nr >= grid.len() as isize || nc < 0 || nc >= grid[0].len() as isize {
-                    continue;
-                }
-
-                let (nr, nc) = (nr as usize, nc as usize);
-
-                match grid[nr][nc] {
-                    '#' => {
-                        //visited[y][x] = true;
-                        //plots_reached.insert((y, x));
-                    }
-                    '.' => {
-                        queue.push((nr, nc));
-                        plots_reached.insert((nr, nc));
-                    }
-                    _ => {}
-                }
-            }
+/// Counts the plots that can be reached in exactly `steps` steps on the
+/// infinitely repeating garden.
+fn reachable(grid: &Grid<char>, start: (usize, usize), steps: usize) -> usize {
+    let start = (start.0 as isize, start.1 as isize);
+    let mut visited: HashSet<_> = [start].into_iter().collect();
+    let mut queue = VecDeque::from([(start, 0)]);
+    let mut count = 0;
 
-            steps += 1;
+    while let Some((pos, dist)) = queue.pop_front() {
+        // any plot reached in fewer steps can be revisited by stepping back and forth
+        if dist % 2 == steps % 2 {
+            count += 1;
+        }
+        if dist == steps {
+            continue;
         }
 
-        // if y < grid.len() - 1 && grid[y + 1][x] != '#' {
-        //     queue.push((y + 1, x));
-        // }
-        // if x > 0 && grid[y][x - 1] != '#' {
-        //     queue.push((y, x - 1));
-        // }
-        // if x < grid[y].len() - 1 && grid[y][x + 1] != '#' {
-        //     queue.push((y, x + 1));
-        // }
-    }
-
-    //println!("{:?}", plots_reached);
-    //println!("{:?}", visited);
-
-    let mut debug = grid.clone();
-    for (y, row) in debug.iter_mut().enumerate() {
-        for (x, c) in row.iter_mut().enumerate() {
-            if visited[y][x] {
-                *c = 'O';
+        for next in grid.tiled_neighbors4(pos) {
+            if grid[next.local] != '#' && visited.insert(next.global) {
+                queue.push_back((next.global, dist + 1));
             }
         }
     }
 
-    for row in debug {
-        for c in row {
-            print!("{}", c);
-        }
-        println!();
+    count
+}
+
+/// The input has clear rows and columns through `S`, so the reachable count
+/// grows quadratically in the number of whole tiles crossed.
+fn reachable_extrapolated(grid: &Grid<char>, start: (usize, usize), steps: usize) -> usize {
+    let size = grid.width();
+    let rem = steps % size;
+    let n = steps / size;
+
+    let a0 = reachable(grid, start, rem);
+    let a1 = reachable(grid, start, rem + size);
+    let a2 = reachable(grid, start, rem + 2 * size);
+
+    let d1 = a1 - a0;
+    let d2 = a2 + a0 - 2 * a1;
+
+    a0 + n * d1 + n * (n - 1) / 2 * d2
+}
+
+#[aoc::main(21)]
+pub fn main(input: &str) -> (usize, usize) {
+    let grid = Grid::<char>::from(input);
+    let start = find_start(&grid);
+
+    (
+        reachable(&grid, start, P1_STEPS),
+        reachable_extrapolated(&grid, start, P2_STEPS),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r"...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn p1_example() {
+        let grid = Grid::<char>::from(EXAMPLE);
+        assert_eq!(reachable(&grid, find_start(&grid), 6), 16);
     }
 
-    (0, 0)
+    #[test]
+    fn p2_example() {
+        let grid = Grid::<char>::from(EXAMPLE);
+        let start = find_start(&grid);
+        assert_eq!(reachable(&grid, start, 10), 50);
+        assert_eq!(reachable(&grid, start, 50), 1594);
+        assert_eq!(reachable(&grid, start, 100), 6536);
+    }
 }
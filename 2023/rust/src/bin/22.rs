@@ -1,6 +1,5 @@
 #[aoc::main(22)]
 fn main(input: &str) -> (usize, usize) {
-    let input = std::fs::read_to_string("../inputs/22.test").unwrap();
     let bricks = input
         .lines()
         .map(|line| {
@@ -24,7 +23,7 @@ fn main(input: &str) -> (usize, usize) {
         })
         .collect::<Vec<_>>();
 
-    println!("{:?}", bricks);
+    eprintln!("{:?}", bricks);
 
     (0, 0)
 }
@@ -0,0 +1,271 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// Where the Advent of Code site is and how to sign in to it, from
+/// `~/.config/aoc/config.toml` (under `$XDG_CONFIG_HOME` if set), with the
+/// `AOC_SESSION`, `AOC_BASE_URL` and `AOC_CONTACT` variables taking precedence:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// contact = "you@example.com"
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// The `session` cookie of a browser that's logged in to the site.
+    pub session: Option<String>,
+    /// The site to talk to, which tests point at a local server.
+    pub base_url: Option<String>,
+    /// Added to the User-Agent so the site's admins can reach whoever's
+    /// running this.
+    pub contact: Option<String>,
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        let dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| Some(PathBuf::from(env::var_os("HOME")?).join(".config")))?;
+        Some(dir.join("aoc/config.toml"))
+    }
+
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let mut config = Config::default();
+        if let Some(path) = Self::path() {
+            match fs::read_to_string(&path) {
+                Ok(contents) => {
+                    let table: toml::Table = contents
+                        .parse()
+                        .map_err(|e| format!("{}: {e}", path.display()))?;
+                    let get = |key| table.get(key)?.as_str().map(|s| s.trim().to_string());
+                    config.session = get("session");
+                    config.base_url = get("base_url");
+                    config.contact = get("contact");
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("{}: {e}", path.display()).into()),
+            }
+        }
+        for (var, setting) in [
+            ("AOC_SESSION", &mut config.session),
+            ("AOC_BASE_URL", &mut config.base_url),
+            ("AOC_CONTACT", &mut config.contact),
+        ] {
+            if let Ok(value) = env::var(var) {
+                *setting = Some(value.trim().to_string());
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// A response's status code and body.
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// A polite client for the Advent of Code site: it says who it is, sends the
+/// session cookie and leaves `interval` between requests. Requests go through
+/// `curl`, which keeps TLS out of this crate.
+pub struct Client {
+    base_url: String,
+    session: String,
+    user_agent: String,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self, String> {
+        let session = config.session.clone().ok_or_else(|| {
+            let path = Config::path().unwrap_or_default();
+            format!(
+                "no session token, set AOC_SESSION or `session` in {}",
+                path.display()
+            )
+        })?;
+        let mut user_agent = format!("aoc/{} (personal puzzle runner", env!("CARGO_PKG_VERSION"));
+        if let Some(contact) = &config.contact {
+            user_agent.push_str(&format!("; {contact}"));
+        }
+        user_agent.push(')');
+        Ok(Client {
+            base_url: config.base_url.as_deref().unwrap_or(BASE_URL).to_string(),
+            session,
+            user_agent,
+            interval: Duration::from_secs(5),
+            last_request: None,
+        })
+    }
+
+    /// Downloads a day's puzzle input.
+    pub fn input(&mut self, year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+        let response = self.request(&format!("/{year}/day/{day}/input"), &[])?;
+        match response.status {
+            200 => Ok(response.body),
+            404 => Err("no input yet, the puzzle may still be locked".into()),
+            status => {
+                let message = response.body.lines().next().unwrap_or_default();
+                Err(format!("HTTP {status}: {message}").into())
+            }
+        }
+    }
+
+    /// Submits an answer and returns the page the site replies with.
+    pub fn answer(
+        &mut self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let path = format!("/{year}/day/{day}/answer");
+        let response = self.request(&path, &[("level", &part.to_string()), ("answer", answer)])?;
+        match response.status {
+            200 => Ok(response.body),
+            status => Err(format!("HTTP {status} from {path}").into()),
+        }
+    }
+
+    /// Sends a GET request, or a POST of `form` if it isn't empty.
+    fn request(&mut self, path: &str, form: &[(&str, &str)]) -> Result<Response, Box<dyn Error>> {
+        if let Some(last) = self.last_request {
+            thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+        let url = format!("{}{path}", self.base_url.trim_end_matches('/'));
+
+        // The cookie goes in on stdin so it doesn't show up in the process list.
+        let mut curl = Command::new("curl")
+            .args(["--silent", "--show-error", "--max-time", "30"])
+            .args(["--header", "@-", "--user-agent", &self.user_agent])
+            .args(form.iter().flat_map(|(key, value)| {
+                ["--data-urlencode".to_string(), format!("{key}={value}")]
+            }))
+            .args(["--write-out", "\n%{http_code}", &url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to run curl: {e}"))?;
+        let cookie = format!("Cookie: session={}\n", self.session);
+        curl.stdin.take().unwrap().write_all(cookie.as_bytes())?;
+        let output = curl.wait_with_output()?;
+        self.last_request = Some(Instant::now());
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("{url}: {}", stderr.trim()).into());
+        }
+        let output = String::from_utf8(output.stdout)?;
+        let (body, status) = output.rsplit_once('\n').ok_or("no status from curl")?;
+        Ok(Response {
+            status: status.parse()?,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// A stand-in for the site that serves canned responses.
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read},
+        net::TcpListener,
+    };
+
+    /// Answers one request with each of `responses` in turn, then returns the
+    /// requests it got. Returns the server's base URL.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let header = line.to_ascii_lowercase();
+                    if let Some(value) = header.strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {status} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    pub fn client(url: String) -> Client {
+        let config = Config {
+            session: Some("cookie".to_string()),
+            base_url: Some(url),
+            contact: Some("me@example.com".to_string()),
+        };
+        let mut client = Client::new(&config).unwrap();
+        client.interval = Duration::ZERO;
+        client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fetches_inputs_politely() {
+        let (url, server) = mock::serve(vec![(200, "1 2 3\n"), (404, "Not Found")]);
+        let mut client = mock::client(url);
+        client.interval = Duration::from_millis(200);
+        assert_eq!(client.input(2024, 9).unwrap(), "1 2 3\n");
+        let start = Instant::now();
+        assert!(client.input(2024, 25).is_err());
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/9/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=cookie\r\n"));
+        assert!(requests[0]
+            .contains("User-Agent: aoc/0.1.0 (personal puzzle runner; me@example.com)\r\n"));
+    }
+
+    #[test]
+    fn posts_answers() {
+        let (url, server) = mock::serve(vec![(200, "<article>Right</article>")]);
+        let page = mock::client(url).answer(2023, 19, 2, "a&b 1").unwrap();
+        assert_eq!(page, "<article>Right</article>");
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("POST /2023/day/19/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=a%26b+1"));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::client::{Client, Config};
+use crate::solutions::{self, Kind, Selection, Solution};
+use crate::USAGE;
+use itertools::Itertools;
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// `aoc fetch <YEAR[:DAYS]>...`: downloads the puzzle inputs that aren't on
+/// disk yet. Whole years stop at the first puzzle that's still locked.
+pub fn fetch(
+    args: impl IntoIterator<Item = String>,
+    root: &Path,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let selections: Result<Vec<Selection>, String> = args.into_iter().map(|a| a.parse()).collect();
+    let selections = match selections {
+        Ok(selections) if !selections.is_empty() => selections,
+        Ok(_) => {
+            eprintln!("{USAGE}");
+            return Ok(ExitCode::from(2));
+        }
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return Ok(ExitCode::from(2));
+        }
+    };
+
+    let now = SystemTime::now();
+    let mut client = None;
+    let mut ok = true;
+    for selection in &selections {
+        let year = selection.year;
+        if !solutions::crate_dir(root, year)
+            .join("Cargo.toml")
+            .is_file()
+        {
+            return Err(format!("no {year}/rust crate in {}", root.display()).into());
+        }
+        let solutions = solutions::solutions(root, year)?;
+        for day in (1..=last_day(year)).filter(|&day| selection.contains(day)) {
+            if selection.days.is_empty() && !unlocked(year, day, now) {
+                break;
+            }
+            let targets = targets(root, year, day, &solutions);
+            let fetched = fetch_day(&targets, || {
+                if !unlocked(year, day, now) {
+                    return Err("the puzzle is still locked".into());
+                }
+                let client = match &mut client {
+                    Some(client) => client,
+                    None => client.insert(Client::new(&Config::load()?)?),
+                };
+                client.input(year, day)
+            });
+            match fetched {
+                Ok(Fetched::Cached) => println!("{year} day {day}: cached"),
+                Ok(Fetched::Copied) => println!("{year} day {day}: copied from cache"),
+                Ok(Fetched::Downloaded) => println!("{year} day {day}: downloaded"),
+                Err(e) => {
+                    eprintln!("{year} day {day}: {e}");
+                    ok = false;
+                }
+            }
+        }
+    }
+    Ok(if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Every file a day's input should be in: the inputs of its solutions, or
+/// where a new solution would look for it.
+fn targets(root: &Path, year: u32, day: u32, solutions: &[Solution]) -> Vec<PathBuf> {
+    let targets: Vec<PathBuf> = solutions
+        .iter()
+        .filter(|solution| solution.day == day)
+        .map(|solution| solution.input(root))
+        .unique()
+        .collect();
+    if !targets.is_empty() {
+        return targets;
+    }
+    let kind = if solutions::has_runner(root, year) {
+        Kind::Runner
+    } else {
+        Kind::Bin(format!("{day:02}"))
+    };
+    vec![Solution { year, day, kind }.input(root)]
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Fetched {
+    /// Every target already had the input.
+    Cached,
+    /// Some targets were missing the input and got it from another.
+    Copied,
+    Downloaded,
+}
+
+/// Makes sure every file in `targets` holds the day's input, copying it from
+/// one that already does or downloading it with `download` if none do. Empty
+/// files, like the ones `aoc new` creates, don't count.
+fn fetch_day(
+    targets: &[PathBuf],
+    download: impl FnOnce() -> Result<String, Box<dyn Error>>,
+) -> Result<Fetched, Box<dyn Error>> {
+    let missing = |path: &&PathBuf| fs::metadata(path).map_or(true, |m| m.len() == 0);
+    let cached = targets.iter().find(|path| !missing(path));
+    let (input, mut fetched) = match cached {
+        Some(path) => (fs::read_to_string(path)?, Fetched::Cached),
+        None => (download()?, Fetched::Downloaded),
+    };
+    for target in targets.iter().filter(missing) {
+        fs::create_dir_all(target.parent().unwrap())?;
+        fs::write(target, &input)?;
+        if fetched == Fetched::Cached {
+            fetched = Fetched::Copied;
+        }
+    }
+    Ok(fetched)
+}
+
+/// The last day of a year's event, which went from 25 days to 12 in 2025.
+pub fn last_day(year: u32) -> u32 {
+    if year < 2025 {
+        25
+    } else {
+        12
+    }
+}
+
+/// Whether a puzzle is out by `now`. They unlock at midnight EST, 05:00 UTC.
+fn unlocked(year: u32, day: u32, now: SystemTime) -> bool {
+    // Days since the epoch for a December date, from Howard Hinnant's
+    // `days_from_civil`.
+    let year = u64::from(year);
+    let year_of_era = year % 400;
+    let day_of_year = 275 + u64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = year / 400 * 146_097 + day_of_era - 719_468;
+    now >= UNIX_EPOCH + Duration::from_secs(days * 86_400 + 5 * 3_600)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock;
+
+    #[test]
+    fn release_times() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        assert!(unlocked(2024, 1, at(1_733_029_200)));
+        assert!(!unlocked(2024, 1, at(1_733_029_199)));
+        assert!(!unlocked(2024, 2, at(1_733_029_200)));
+        assert!(unlocked(2015, 25, at(1_733_029_200)));
+    }
+
+    #[test]
+    fn never_refetches() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let targets = [dir.join("inputs/09.txt"), dir.join("input/2024/day9.txt")];
+
+        let (url, server) = mock::serve(vec![(200, "2333133121414131402\n")]);
+        let mut client = mock::client(url);
+        let download = || client.input(2024, 9);
+        assert_eq!(fetch_day(&targets, download).unwrap(), Fetched::Downloaded);
+        assert_eq!(server.join().unwrap().len(), 1);
+        for target in &targets {
+            assert_eq!(fs::read_to_string(target).unwrap(), "2333133121414131402\n");
+        }
+
+        let unreachable = || -> Result<String, Box<dyn Error>> { panic!("refetched") };
+        assert_eq!(fetch_day(&targets, unreachable).unwrap(), Fetched::Cached);
+        fs::write(&targets[1], "").unwrap();
+        assert_eq!(fetch_day(&targets, unreachable).unwrap(), Fetched::Copied);
+        assert_eq!(
+            fs::read_to_string(&targets[1]).unwrap(),
+            "2333133121414131402\n"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,30 +1,48 @@
-use itertools::Itertools;
-use std::{error::Error, fs, process::Command};
+mod client;
+mod input;
+mod new;
+mod puzzle;
+mod run;
+mod solutions;
+mod submit;
 
-fn extract_microseconds(output: &str) -> Result<usize, Box<dyn Error>> {
-    let out = output.lines().last().unwrap();
-    let time = if out.ends_with("ms") {
-        out["Time: ".len()..out.len() - 2].parse::<usize>()? * 1000
-    } else {
-        out["Time: ".len()..out.len() - 3].parse::<usize>()?
-    };
-    Ok(time)
-}
+use std::{error::Error, path::Path, process::ExitCode};
+
+const USAGE: &str = "\
+usage: aoc run [YEAR[:DAYS]]... [--timeout <seconds>] [--jobs <n>]
+       aoc new <year> <day>
+       aoc fetch <YEAR[:DAYS]>...
+       aoc examples <year> <day> <page.html>
+       aoc submit <year> <day> <part> <answer>
+
+`run` runs the solutions for every year, or for the given years and days,
+e.g. `aoc run 2024` or `aoc run 2023:5-10,12`.
+`new` adds a day to an aoc-runner year from its template.
+`fetch` downloads missing puzzle inputs, signing in with the session token
+in AOC_SESSION or ~/.config/aoc/config.toml.
+`examples` saves the examples and their answers from a saved puzzle page to
+DD.example[.n] and DD.expected next to the day's input, which `new` fills in
+and `--example [n]` reads.
+`submit` sends an answer unless <year>/guesses.toml shows it's wrong, records
+the verdict there and, once it's right, in <year>/answers.toml.";
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    // This crate is `<root>/2023/rust`, next to the other years.
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .unwrap();
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let days = fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/"))?
-        .filter_map(|p| p.ok()?.path().file_stem()?.to_str().map(str::to_string))
-        .sorted()
-        .collect::<Vec<_>>();
-    let mut total_time = 0;
-    for day in &days {
-        let cmd = Command::new("cargo")
-            .args(["run", "--release", "--bin", day])
-            .output()?;
-        let output = String::from_utf8(cmd.stdout)?;
-        println!("Day {}:\n{}", day, output);
-        total_time += extract_microseconds(&output)?;
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("run") => run::run(args, root),
+        Some("new") => new::new(args, root),
+        Some("fetch") => input::fetch(args, root),
+        Some("examples") => puzzle::examples(args, root),
+        Some("submit") => submit::submit(args, root),
+        _ => {
+            eprintln!("{USAGE}");
+            Ok(ExitCode::from(2))
+        }
     }
-    println!("Total time: {}ms", total_time / 1000);
-    Ok(())
 }
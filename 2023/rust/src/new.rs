@@ -0,0 +1,165 @@
+use crate::input;
+use crate::puzzle;
+use crate::solutions;
+use crate::USAGE;
+use std::{error::Error, fs, path::Path, process::ExitCode};
+
+/// `aoc new <year> <day>`: renders the year's `*.rs.tpl` into `src/dayN.rs`,
+/// declares the module in `lib.rs` and creates an empty puzzle input.
+pub fn new(
+    args: impl IntoIterator<Item = String>,
+    root: &Path,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let args: Vec<String> = args.into_iter().collect();
+    let (year, day) = match &args[..] {
+        [year, day] => (year.parse::<u32>().ok(), day.parse::<u32>().ok()),
+        _ => (None, None),
+    };
+    let (Some(year), Some(day)) = (year, day) else {
+        eprintln!("{USAGE}");
+        return Ok(ExitCode::from(2));
+    };
+    if !(1..=input::last_day(year)).contains(&day) {
+        eprintln!("{year} has days 1 to {}", input::last_day(year));
+        return Ok(ExitCode::from(2));
+    }
+
+    let dir = solutions::crate_dir(root, year);
+    let template = template(&dir)?.ok_or_else(|| format!("no *.rs.tpl in {}", dir.display()))?;
+    let module = dir.join(format!("src/day{day}.rs"));
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()).into());
+    }
+    let lib_path = dir.join("src/lib.rs");
+    let lib = insert_mod(&fs::read_to_string(&lib_path)?, day)?;
+
+    // The example and its answers, if `aoc examples` has saved them.
+    let example = fs::read_to_string(puzzle::example_path(root, year, day, 1)).unwrap_or_default();
+    let answers = puzzle::expected(root, year, day)?;
+    let input = dir.join(format!("input/{year}/day{day}.txt"));
+
+    fs::write(
+        &module,
+        render(&template, day, example.trim_end(), &answers),
+    )?;
+    fs::write(&lib_path, lib)?;
+    if !input.exists() {
+        fs::create_dir_all(input.parent().unwrap())?;
+        fs::write(&input, "")?;
+    }
+    println!("Created {}", module.display());
+    println!("Paste the puzzle input into {}", input.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The day template in a year's crate, `day.rs.tpl` or similar.
+fn template(dir: &Path) -> Result<Option<String>, Box<dyn Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.to_string_lossy().ends_with(".rs.tpl") {
+            return Ok(Some(fs::read_to_string(path)?));
+        }
+    }
+    Ok(None)
+}
+
+/// Fills in a day template. `{DAY}` is the aoc-runner day, e.g. `day9`, and
+/// `<PART1>` and `<PART2>` are the answers to `<EXAMPLE>`, `0` if unknown.
+fn render(template: &str, day: u32, example: &str, answers: &[Option<String>; 2]) -> String {
+    // Answers that aren't numbers are compared as strings.
+    let [part1, part2] = answers.clone().map(|answer| match answer {
+        Some(answer) if answer.parse::<i64>().is_err() => format!("{answer:?}"),
+        answer => answer.unwrap_or_else(|| "0".to_string()),
+    });
+    template
+        .replace("{DAY}", &format!("day{day}"))
+        .replace("<EXAMPLE>", example)
+        .replace("<PART1>", &part1)
+        .replace("<PART2>", &part2)
+}
+
+/// Adds `mod dayN;` to the block of day modules at the top of `lib`, between
+/// the days either side of it whether the block counts up or down. The other
+/// lines are left as they are.
+fn insert_mod(lib: &str, day: u32) -> Result<String, String> {
+    if solutions::library_days(lib).any(|declared| declared == day) {
+        return Err(format!("lib.rs already declares mod day{day}"));
+    }
+    // The start and end of each line in the block, and its day.
+    let block: Vec<(usize, usize, u32)> = lib
+        .split_inclusive('\n')
+        .scan(0, |end, line| {
+            let start = *end;
+            *end += line.len();
+            Some((start, *end, line))
+        })
+        .map_while(|(start, end, line)| Some((start, end, solutions::module_day(line)?)))
+        .collect();
+
+    let descending = block.first().map(|first| first.2) > block.last().map(|last| last.2);
+    let next = block
+        .iter()
+        .find(|&&(.., other)| if descending { other < day } else { other > day });
+    let at = match (next, block.last()) {
+        (Some(&(start, ..)), _) => start,
+        (None, Some(&(_, end, _))) => end,
+        (None, None) => 0,
+    };
+
+    let mut line = format!("mod day{day};\n");
+    if !lib[..at].is_empty() && !lib[..at].ends_with('\n') {
+        line.insert(0, '\n');
+    }
+    let mut out = lib.to_string();
+    out.insert_str(at, &line);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_template() {
+        let template =
+            "#[aoc({DAY}, part1)]\nconst EXAMPLE: &str = r#\"<EXAMPLE>\"#;\n<PART1> <PART2>";
+        let answers = [Some("-3".to_string()), Some("LJM".to_string())];
+        assert_eq!(
+            render(template, 6, "1 2\n3 4", &answers),
+            "#[aoc(day6, part1)]\nconst EXAMPLE: &str = r#\"1 2\n3 4\"#;\n-3 \"LJM\""
+        );
+        assert!(render(template, 6, "", &[None, None]).ends_with("0 0"));
+    }
+
+    #[test]
+    fn inserts_mod_in_order() {
+        let lib = "mod day1;\nmod day2;\nmod day10;\n\naoc_lib! { year = 2025 }\n";
+        assert_eq!(
+            insert_mod(lib, 3).unwrap(),
+            "mod day1;\nmod day2;\nmod day3;\nmod day10;\n\naoc_lib! { year = 2025 }\n"
+        );
+        assert_eq!(
+            insert_mod("aoc_lib! {}\n", 1).unwrap(),
+            "mod day1;\naoc_lib! {}\n"
+        );
+        assert!(insert_mod(lib, 2).is_err());
+        assert!(insert_mod("pub mod day2;\n", 2).is_err());
+    }
+
+    #[test]
+    fn inserts_mod_without_reordering() {
+        let lib = "mod day25;\nmod day10;\nmod day11;\npub mod day9;\n#[macro_use]\n";
+        assert_eq!(
+            insert_mod(lib, 8).unwrap(),
+            "mod day25;\nmod day10;\nmod day11;\npub mod day9;\nmod day8;\n#[macro_use]\n"
+        );
+        assert_eq!(
+            insert_mod(lib, 12).unwrap(),
+            "mod day25;\nmod day12;\nmod day10;\nmod day11;\npub mod day9;\n#[macro_use]\n"
+        );
+        assert_eq!(
+            insert_mod("mod day2;", 3).unwrap(),
+            "mod day2;\nmod day3;\n"
+        );
+    }
+}
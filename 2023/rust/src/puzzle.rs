@@ -0,0 +1,217 @@
+use crate::USAGE;
+use crate::{input, solutions};
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// What a puzzle page gives away: its examples and, for each part shown, the
+/// answer to the example.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub examples: Vec<String>,
+    pub answers: Vec<String>,
+}
+
+impl Puzzle {
+    /// Reads a saved puzzle page. Each part is an `<article>` whose examples
+    /// are in `<pre><code>` blocks and whose answer to them is the last
+    /// emphasised code, `<code><em>42</em></code>`. Blocks with emphasis in
+    /// them walk through an example rather than give one, so are skipped.
+    pub fn parse(html: &str) -> Self {
+        let mut puzzle = Puzzle::default();
+        let articles = between(html, "<article", "</article>");
+        let parts = if articles.is_empty() {
+            vec![html]
+        } else {
+            articles
+        };
+        for part in parts {
+            for example in between(part, "<pre><code>", "</code></pre>") {
+                if example.contains("<em>") {
+                    continue;
+                }
+                let example = text(example);
+                if !puzzle.examples.contains(&example) {
+                    puzzle.examples.push(example);
+                }
+            }
+            let emphasised = [
+                ("<code><em>", "</em></code>"),
+                ("<em><code>", "</code></em>"),
+            ]
+            .into_iter()
+            .filter_map(|(open, close)| {
+                let last = part.rfind(open)?;
+                Some((last, *between(&part[last..], open, close).first()?))
+            })
+            .max();
+            if let Some((_, answer)) = emphasised {
+                puzzle.answers.push(text(answer));
+            }
+        }
+        puzzle
+    }
+}
+
+/// Every piece of `html` between `open` and the `close` after it.
+pub fn between<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut pieces = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        rest = &rest[start + open.len()..];
+        let Some(end) = rest.find(close) else { break };
+        pieces.push(&rest[..end]);
+        rest = &rest[end + close.len()..];
+    }
+    pieces
+}
+
+/// The text of some HTML: tags dropped and entities decoded.
+pub fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..].split_once('>').map_or("", |(_, rest)| rest);
+    }
+    text.push_str(rest);
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Where the `n`th example of a day is kept, counting from one, next to the
+/// input the day reads: `<year>/inputs/DD.example`, then `DD.example.2` and so
+/// on, or `input/<year>/dayN.example` in an aoc-runner crate.
+pub fn example_path(root: &Path, year: u32, day: u32, n: usize) -> PathBuf {
+    match n {
+        1 => day_file(root, year, day, "example"),
+        n => day_file(root, year, day, &format!("example.{n}")),
+    }
+}
+
+/// Where the answers to a day's examples are kept, `DD.expected` or
+/// `dayN.expected` next to the examples, as `part1 = "..."` and `part2 = "..."`.
+pub fn expected_path(root: &Path, year: u32, day: u32) -> PathBuf {
+    day_file(root, year, day, "expected")
+}
+
+/// A file with the given extension in the folder a day's input is read from.
+fn day_file(root: &Path, year: u32, day: u32, extension: &str) -> PathBuf {
+    if solutions::has_runner(root, year) {
+        let dir = solutions::crate_dir(root, year).join(format!("input/{year}"));
+        dir.join(format!("day{day}.{extension}"))
+    } else {
+        let dir = root.join(year.to_string()).join("inputs");
+        dir.join(format!("{day:02}.{extension}"))
+    }
+}
+
+/// The answers to a day's examples, if they've been extracted.
+pub fn expected(root: &Path, year: u32, day: u32) -> Result<[Option<String>; 2], Box<dyn Error>> {
+    let path = expected_path(root, year, day);
+    let table: toml::Table = match fs::read_to_string(&path) {
+        Ok(contents) => contents
+            .parse()
+            .map_err(|e| format!("{}: {e}", path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let answer = |part| table.get(part)?.as_str().map(str::to_string);
+    Ok([answer("part1"), answer("part2")])
+}
+
+/// `aoc examples <year> <day> <page.html>`: saves the examples and their
+/// answers from a saved puzzle page for `aoc new` and `example_file`.
+pub fn examples(
+    args: impl IntoIterator<Item = String>,
+    root: &Path,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let args: Vec<String> = args.into_iter().collect();
+    let (year, day, page) = match &args[..] {
+        [year, day, page] => (year.parse::<u32>().ok(), day.parse::<u32>().ok(), page),
+        _ => (None, None, &String::new()),
+    };
+    let (Some(year), Some(day)) = (year, day) else {
+        eprintln!("{USAGE}");
+        return Ok(ExitCode::from(2));
+    };
+    if !(1..=input::last_day(year)).contains(&day) {
+        eprintln!("{year} has days 1 to {}", input::last_day(year));
+        return Ok(ExitCode::from(2));
+    }
+
+    let html = fs::read_to_string(page).map_err(|e| format!("{page}: {e}"))?;
+    let puzzle = Puzzle::parse(&html);
+    if puzzle.examples.is_empty() {
+        return Err(format!("no <pre><code> examples in {page}").into());
+    }
+    fs::create_dir_all(expected_path(root, year, day).parent().unwrap())?;
+    for (i, example) in puzzle.examples.iter().enumerate() {
+        let path = example_path(root, year, day, i + 1);
+        fs::write(&path, example)?;
+        println!("Wrote {}", path.display());
+    }
+
+    let mut expected = String::new();
+    for (part, answer) in puzzle.answers.iter().enumerate() {
+        let answer = answer.replace('\\', "\\\\").replace('"', "\\\"");
+        expected.push_str(&format!("part{} = \"{answer}\"\n", part + 1));
+    }
+    let path = expected_path(root, year, day);
+    fs::write(&path, expected)?;
+    println!("Wrote {}", path.display());
+    for (part, answer) in ["one", "two"].iter().zip(&puzzle.answers) {
+        println!("Part {part} of the example: {answer}");
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 9: Mirage Maintenance ---</h2>
+<p>For example:</p>
+<pre><code>0 3 6 9 12 15
+1 3 6 10 15 21
+</code></pre>
+<p>Here, the sequence is <code>0 3 6</code> and the next value is <code><em>18</em></code>.</p>
+<p>The sum of these is <code><em>114</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1641934234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>0 3 6 9 12 15
+1 3 6 10 15 21
+</code></pre>
+<pre><code><em>5</em>  10  13  &lt;16&gt;
+</code></pre>
+<p>Adding the new values gives <em><code>2</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn examples_and_answers() {
+        assert_eq!(
+            Puzzle::parse(PAGE),
+            Puzzle {
+                examples: vec!["0 3 6 9 12 15\n1 3 6 10 15 21\n".to_string()],
+                answers: vec!["114".to_string(), "2".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn part_one_only() {
+        let page = &PAGE[..PAGE.find("<p>Your puzzle answer").unwrap()];
+        let puzzle = Puzzle::parse(page);
+        assert_eq!(puzzle.examples.len(), 1);
+        assert_eq!(puzzle.answers, ["114"]);
+    }
+}
@@ -0,0 +1,436 @@
+use crate::solutions::{self, Selection, Solution};
+use crate::USAGE;
+use itertools::Itertools;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use toml::Table;
+
+struct Options {
+    /// What to run, everything if empty.
+    selections: Vec<Selection>,
+    timeout: Duration,
+    jobs: usize,
+}
+
+impl Options {
+    fn selects(&self, solution: &Solution) -> bool {
+        self.selections.is_empty()
+            || self.selections.iter().any(|selection| {
+                selection.year == solution.year && selection.contains(solution.day)
+            })
+    }
+}
+
+fn parse_options(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        selections: Vec::new(),
+        timeout: Duration::from_secs(60),
+        jobs: 1,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--timeout" => {
+                let secs = args.next().and_then(|s| s.parse::<f64>().ok());
+                let secs = secs.filter(|&s| s > 0.0).ok_or("--timeout needs seconds")?;
+                options.timeout = Duration::from_secs_f64(secs);
+            }
+            "--jobs" | "-j" => {
+                let jobs = args.next().and_then(|n| n.parse().ok());
+                options.jobs = jobs.filter(|&n| n > 0).ok_or("--jobs needs a count")?;
+            }
+            selection => options.selections.push(selection.parse()?),
+        }
+    }
+    Ok(options)
+}
+
+/// One day's answers and timings, as printed by a day binary run with
+/// `--format json`.
+struct Report {
+    part1: String,
+    part2: String,
+    total_ns: u64,
+}
+
+fn parse_report(output: &str) -> Result<Report, Box<dyn Error>> {
+    let line = output
+        .lines()
+        .rfind(|line| line.starts_with('{'))
+        .ok_or("no JSON report in output")?;
+    let json: Value = serde_json::from_str(line)?;
+    let answer = |key: &str| {
+        json[key]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| format!("missing {key}"))
+    };
+    Ok(Report {
+        part1: answer("part1")?,
+        part2: answer("part2")?,
+        total_ns: json["total_ns"].as_u64().ok_or("missing total_ns")?,
+    })
+}
+
+/// How running a solution went.
+enum Outcome {
+    Done(Report),
+    Failed {
+        status: &'static str,
+        message: String,
+    },
+}
+
+impl Outcome {
+    fn failed(status: &'static str, message: impl ToString) -> Self {
+        Outcome::Failed {
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Runs a solution on the puzzle input, killing it after `timeout`.
+fn run_solution(mut command: Command, timeout: Duration) -> Outcome {
+    let child = command
+        .args(["--format", "json"])
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            let program = Path::new(command.get_program()).display();
+            return Outcome::failed("ERROR", format!("{program}: {e}"));
+        }
+    };
+
+    // Drain both pipes while waiting so a chatty day can't fill them and stall.
+    fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let mut out = String::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_string(&mut out);
+            }
+            out
+        })
+    }
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Outcome::failed("TIMEOUT", format!("killed after {timeout:?}"));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(5)),
+            Err(e) => return Outcome::failed("ERROR", e),
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        // A panic's message is on the line after "thread 'main' panicked at".
+        let mut lines = stderr.lines().filter(|line| !line.is_empty());
+        let message = match lines.clone().position(|line| line.contains("panicked at")) {
+            Some(at) => lines.nth(at + 1),
+            None => lines.rfind(|line| !line.starts_with("note:")),
+        };
+        let message = message.unwrap_or("no output on stderr");
+        let status = if status.code() == Some(101) {
+            "PANIC"
+        } else {
+            "ERROR"
+        };
+        return Outcome::failed(status, message);
+    }
+    match parse_report(&stdout) {
+        Ok(report) => Outcome::Done(report),
+        Err(e) => Outcome::failed("ERROR", e),
+    }
+}
+
+/// Where a year's accepted answers are kept.
+pub fn answers_path(root: &Path, year: u32) -> PathBuf {
+    root.join(year.to_string()).join("answers.toml")
+}
+
+/// Answers recorded with `--record`, keyed by zero-padded day and then by part.
+pub fn read_answers(root: &Path, year: u32) -> Result<Table, Box<dyn Error>> {
+    match fs::read_to_string(answers_path(root, year)) {
+        Ok(contents) => Ok(contents.parse()?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Table::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Status {
+    Pass,
+    /// Holds the recorded answer.
+    Fail(String),
+    Unknown,
+}
+
+fn check(answers: &Table, day: &str, part: &str, answer: &str) -> Status {
+    let expected = match answers.get(day).and_then(|parts| parts.get(part)) {
+        Some(toml::Value::String(expected)) => expected.clone(),
+        Some(toml::Value::Integer(expected)) => expected.to_string(),
+        _ => return Status::Unknown,
+    };
+    if expected == answer {
+        Status::Pass
+    } else {
+        Status::Fail(expected)
+    }
+}
+
+fn format_ns(ns: u64) -> String {
+    if ns >= 1_000_000 {
+        format!("{:.3}ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.3}μs", ns as f64 / 1e3)
+    }
+}
+
+/// A summary table row: year, solution, status, part one, part two and time.
+type Row = [String; 6];
+
+/// Where the status is in a [`Row`].
+const STATUS: usize = 2;
+
+fn row(solution: &Solution, outcome: &Outcome, answers: &Table) -> Row {
+    let [year, name] = [solution.year.to_string(), solution.name()];
+    let report = match outcome {
+        Outcome::Done(report) => report,
+        Outcome::Failed { status, message } => {
+            let [status, message] = [status.to_string(), message.clone()];
+            return [year, name, status, message, String::new(), String::new()];
+        }
+    };
+    let day = format!("{:02}", solution.day);
+    let part1 = check(answers, &day, "part1", &report.part1);
+    let part2 = check(answers, &day, "part2", &report.part2);
+    let status = match (&part1, &part2) {
+        (Status::Fail(_), _) | (_, Status::Fail(_)) => "FAIL",
+        (Status::Pass, Status::Pass) => "PASS",
+        _ => "UNKNOWN",
+    };
+    let cell = |answer: &str, status| match status {
+        Status::Fail(expected) => format!("{answer} (expected {expected})"),
+        _ => answer.to_string(),
+    };
+    [
+        year,
+        name,
+        status.to_string(),
+        cell(&report.part1, part1),
+        cell(&report.part2, part2),
+        format_ns(report.total_ns),
+    ]
+}
+
+fn print_table(rows: &[Row]) {
+    let header = ["Year", "Solution", "Status", "Part one", "Part two", "Time"];
+    let header = header.map(str::to_string);
+    let done = |row: &&Row| !row[5].is_empty();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|col| {
+            // Failure messages run on past the answer columns.
+            let rows = rows.iter().filter(|row| col <= STATUS || done(row));
+            let widest = rows.map(|row| row[col].chars().count()).max();
+            widest.unwrap_or(0).max(header[col].len())
+        })
+        .collect();
+    for row in [&header].into_iter().chain(rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+}
+
+/// `aoc run`: builds the selected solutions of every year once, runs each on
+/// its puzzle input and prints a summary table.
+pub fn run(
+    args: impl IntoIterator<Item = String>,
+    root: &Path,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let options = match parse_options(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return Ok(ExitCode::from(2));
+        }
+    };
+
+    let years = solutions::years(root)?;
+    if let Some(missing) = options
+        .selections
+        .iter()
+        .find(|selection| !years.contains(&selection.year))
+    {
+        eprintln!("no {}/rust crate in {}", missing.year, root.display());
+        return Ok(ExitCode::from(2));
+    }
+    let mut selected = Vec::new();
+    for &year in &years {
+        let solutions = solutions::solutions(root, year)?;
+        selected.extend(solutions.into_iter().filter(|s| options.selects(s)));
+    }
+    if selected.is_empty() {
+        eprintln!("no solutions match");
+        return Ok(ExitCode::from(2));
+    }
+
+    // Build each year up front so every solution is timed on its own binary.
+    // Each year keeps its own target directory, as their binaries share names.
+    for solutions in selected.chunk_by(|a, b| a.year == b.year) {
+        let year = solutions[0].year;
+        let dir = solutions::crate_dir(root, year);
+        let bins = solutions
+            .iter()
+            .map(Solution::bin)
+            .unique()
+            .collect::<Vec<_>>();
+        let build = Command::new("cargo")
+            .current_dir(&dir)
+            .args(["build", "--release", "--target-dir", "target"])
+            .args(bins.iter().flat_map(|bin| ["--bin", bin]))
+            .status()?;
+        if !build.success() {
+            return Err(format!("build failed for {year}").into());
+        }
+    }
+
+    let outcomes = Mutex::new(Vec::new());
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(selected.len()) {
+            scope.spawn(|| {
+                while let Some(solution) = selected.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let target = solutions::crate_dir(root, solution.year).join("target");
+                    let outcome = run_solution(solution.command(&target), options.timeout);
+                    outcomes.lock().unwrap().push((solution, outcome));
+                }
+            });
+        }
+    });
+    let mut outcomes = outcomes.into_inner().unwrap();
+    outcomes.sort_by_key(|&(solution, _)| solution);
+
+    let mut answers = HashMap::new();
+    for &year in selected.iter().map(|s| &s.year).dedup() {
+        answers.insert(year, read_answers(root, year)?);
+    }
+    let rows: Vec<Row> = outcomes
+        .iter()
+        .map(|(solution, outcome)| row(solution, outcome, &answers[&solution.year]))
+        .collect();
+    print_table(&rows);
+
+    let total_time: u64 = outcomes
+        .iter()
+        .filter_map(|(_, outcome)| match outcome {
+            Outcome::Done(report) => Some(report.total_ns),
+            Outcome::Failed { .. } => None,
+        })
+        .sum();
+    let summary = rows
+        .iter()
+        .map(|row| row[STATUS].as_str())
+        .counts()
+        .into_iter()
+        .sorted()
+        .map(|(status, count)| format!("{count} {status}"))
+        .join(", ");
+    println!("\nTotal time: {} ({summary})", format_ns(total_time));
+
+    let ok = rows
+        .iter()
+        .all(|row| matches!(row[STATUS].as_str(), "PASS" | "UNKNOWN"));
+    Ok(if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::Kind;
+
+    fn options(args: &str) -> Result<Options, String> {
+        parse_options(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn selections() {
+        let parsed = options("2024 2023:3,5-10 --timeout 2.5 -j 4").unwrap();
+        assert_eq!(
+            parsed.selections,
+            [
+                Selection {
+                    year: 2024,
+                    days: vec![]
+                },
+                Selection {
+                    year: 2023,
+                    days: vec![3..=3, 5..=10]
+                },
+            ]
+        );
+        assert_eq!(parsed.timeout, Duration::from_millis(2500));
+        assert_eq!(parsed.jobs, 4);
+
+        let solution = |year, day| Solution {
+            year,
+            day,
+            kind: Kind::Runner,
+        };
+        assert!(parsed.selects(&solution(2024, 25)));
+        assert!(parsed.selects(&solution(2023, 7)));
+        assert!(!parsed.selects(&solution(2023, 4)));
+        assert!(!parsed.selects(&solution(2025, 1)));
+        assert!(parse_options(Vec::new())
+            .unwrap()
+            .selects(&solution(2025, 1)));
+
+        assert!(options("2023:5-").is_err());
+        assert!(options("5-10").is_err());
+        assert!(options("--jobs 0").is_err());
+    }
+
+    #[test]
+    fn reports_skip_debug_output() {
+        let output = "debug\n{\"year\":2023,\"day\":1,\"part1\":\"7\",\"part2\":\"x\",\
+                      \"parse_ns\":null,\"part1_ns\":null,\"part2_ns\":null,\"total_ns\":1500}\n";
+        let report = parse_report(output).unwrap();
+        assert_eq!((report.part1.as_str(), report.part2.as_str()), ("7", "x"));
+        assert_eq!(report.total_ns, 1500);
+        assert!(parse_report("Part one: 7").is_err());
+    }
+}
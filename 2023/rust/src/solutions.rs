@@ -0,0 +1,179 @@
+use std::{
+    fs, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+/// One way of solving a day's puzzle. A year can have several for the same day.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    /// A `#[aoc::main]` or `#[aoc::parse]` binary in `src/bin`, named after its
+    /// zero-padded day.
+    Bin(String),
+    /// A `src/dayN.rs` module of an aoc-runner library, run by the year's
+    /// `runner` binary.
+    Runner,
+}
+
+impl Solution {
+    /// How the solution is shown in tables, e.g. `bin 09` or `day9`.
+    pub fn name(&self) -> String {
+        match &self.kind {
+            Kind::Bin(bin) => format!("bin {bin}"),
+            Kind::Runner => format!("day{}", self.day),
+        }
+    }
+
+    /// The binary that has to be built to run this solution.
+    pub fn bin(&self) -> &str {
+        match &self.kind {
+            Kind::Bin(bin) => bin,
+            Kind::Runner => "runner",
+        }
+    }
+
+    /// The puzzle input the solution reads unless given another.
+    pub fn input(&self, root: &Path) -> PathBuf {
+        let (year, day) = (self.year, self.day);
+        match self.kind {
+            Kind::Bin(_) => root.join(format!("{year}/inputs/{day:02}.txt")),
+            Kind::Runner => crate_dir(root, year).join(format!("input/{year}/day{day}.txt")),
+        }
+    }
+
+    /// Runs the release build of the solution, given the year's target directory.
+    pub fn command(&self, target: &Path) -> Command {
+        let mut command = Command::new(target.join("release").join(self.bin()));
+        if self.kind == Kind::Runner {
+            command.arg(self.day.to_string());
+        }
+        command
+    }
+}
+
+/// A year and some of its days, all of them if `days` is empty. Written
+/// `YEAR` or `YEAR:DAYS`, where `DAYS` is a comma-separated list of days and
+/// `FIRST-LAST` ranges.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Selection {
+    pub year: u32,
+    pub days: Vec<RangeInclusive<u32>>,
+}
+
+impl Selection {
+    pub fn contains(&self, day: u32) -> bool {
+        self.days.is_empty() || self.days.iter().any(|days| days.contains(&day))
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(selection: &str) -> Result<Self, Self::Err> {
+        let (year, days) = match selection.split_once(':') {
+            Some((year, days)) => (year, Some(days)),
+            None => (selection, None),
+        };
+        let year = year.parse().map_err(|_| format!("invalid year {year:?}"))?;
+        let mut ranges = Vec::new();
+        for days in days.into_iter().flat_map(|days| days.split(',')) {
+            let range = match days.split_once('-') {
+                Some((first, last)) => first.parse().ok().zip(last.parse().ok()),
+                None => days.parse().ok().map(|day| (day, day)),
+            };
+            let (first, last) = range.ok_or_else(|| format!("invalid day {days:?}"))?;
+            ranges.push(first..=last);
+        }
+        Ok(Selection { year, days: ranges })
+    }
+}
+
+/// The crate holding a year's solutions.
+pub fn crate_dir(root: &Path, year: u32) -> PathBuf {
+    root.join(year.to_string()).join("rust")
+}
+
+/// Every year under `root` with a `<year>/rust` crate, in order.
+pub fn years(root: &Path) -> io::Result<Vec<u32>> {
+    let mut years = Vec::new();
+    for entry in fs::read_dir(root)? {
+        let Ok(year) = entry?.file_name().to_string_lossy().parse() else {
+            continue;
+        };
+        if crate_dir(root, year).join("Cargo.toml").is_file() {
+            years.push(year);
+        }
+    }
+    years.sort();
+    Ok(years)
+}
+
+/// Whether a year's crate is an aoc-runner library with a `runner` binary.
+pub fn has_runner(root: &Path, year: u32) -> bool {
+    crate_dir(root, year).join("src/bin/runner.rs").is_file()
+}
+
+/// The solutions in a year's crate, ordered by day.
+pub fn solutions(root: &Path, year: u32) -> io::Result<Vec<Solution>> {
+    let src = crate_dir(root, year).join("src");
+    let mut solutions = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(src.join("bin")) {
+        for entry in entries {
+            let path = entry?.path();
+            let Some(bin) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if let Ok(day) = bin.parse() {
+                let kind = Kind::Bin(bin.to_string());
+                solutions.push(Solution { year, day, kind });
+            }
+        }
+    }
+
+    if has_runner(root, year) {
+        let lib = fs::read_to_string(src.join("lib.rs"))?;
+        for day in library_days(&lib) {
+            let kind = Kind::Runner;
+            solutions.push(Solution { year, day, kind });
+        }
+    }
+
+    solutions.sort();
+    Ok(solutions)
+}
+
+/// The days declared as `mod dayN;` in an aoc-runner library.
+pub fn library_days(lib: &str) -> impl Iterator<Item = u32> + '_ {
+    lib.lines().filter_map(module_day)
+}
+
+/// The day of a `mod dayN;` or `pub mod dayN;` line.
+pub fn module_day(line: &str) -> Option<u32> {
+    let line = line.trim().trim_start_matches("pub ");
+    line.strip_prefix("mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_from_lib() {
+        let lib =
+            "mod day25;\npub mod day9;\n// mod day3;\nmod helpers;\n\naoc_lib! { year = 2024 }";
+        assert_eq!(library_days(lib).collect::<Vec<_>>(), [25, 9]);
+    }
+}
@@ -0,0 +1,454 @@
+use crate::client::{Client, Config};
+use crate::input;
+use crate::puzzle;
+use crate::run;
+use crate::USAGE;
+use std::{
+    error::Error,
+    fmt, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use toml::{Table, Value};
+
+/// What the site made of an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way.
+    Wrong,
+    /// Sent too soon after the last wrong answer, so not checked.
+    Wait,
+    /// The part is locked or already solved, so not checked.
+    WrongLevel,
+}
+
+impl Verdict {
+    const ALL: [Verdict; 6] = [
+        Verdict::Correct,
+        Verdict::TooHigh,
+        Verdict::TooLow,
+        Verdict::Wrong,
+        Verdict::Wait,
+        Verdict::WrongLevel,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait => "wait",
+            Verdict::WrongLevel => "wrong level",
+        }
+    }
+
+    /// Reads the verdict from the text of the page the site replies with.
+    fn parse_reply(text: &str) -> Option<Self> {
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("That's not the right answer") {
+            Some(if text.contains("too high") {
+                Verdict::TooHigh
+            } else if text.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            })
+        } else if text.contains("You gave an answer too recently") {
+            Some(Verdict::Wait)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let verdict = Verdict::ALL.into_iter().find(|v| v.name() == s);
+        verdict.ok_or_else(|| format!("unknown verdict {s:?}"))
+    }
+}
+
+/// One submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// When it was submitted, in seconds since the epoch.
+    pub time: u64,
+}
+
+/// Every answer submitted for a year, kept in `<year>/guesses.toml` as
+/// `[[guess]]` tables.
+pub struct Ledger {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl Ledger {
+    pub fn path(root: &Path, year: u32) -> PathBuf {
+        root.join(year.to_string()).join("guesses.toml")
+    }
+
+    pub fn load(path: PathBuf) -> Result<Self, Box<dyn Error>> {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let invalid = |e: &dyn fmt::Display| format!("{}: {e}", path.display());
+        let table: Table = contents.parse().map_err(|e| invalid(&e))?;
+        let mut guesses = Vec::new();
+        for guess in table
+            .get("guess")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let field = |key| {
+                guess
+                    .get(key)
+                    .ok_or_else(|| invalid(&format!("no {key} in a guess")))
+            };
+            let number = |key| -> Result<u64, String> {
+                let n = field(key)?.as_integer().and_then(|n| u64::try_from(n).ok());
+                n.ok_or_else(|| invalid(&format!("{key} isn't a number")))
+            };
+            let string = |key| -> Result<&str, String> {
+                let s = field(key)?.as_str();
+                s.ok_or_else(|| invalid(&format!("{key} isn't a string")))
+            };
+            guesses.push(Guess {
+                day: number("day")? as u32,
+                part: number("part")? as u32,
+                answer: string("answer")?.to_string(),
+                verdict: string("verdict")?.parse().map_err(|e| invalid(&e))?,
+                time: number("time")?,
+            });
+        }
+        Ok(Ledger { path, guesses })
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let guesses = self.guesses.iter().map(|guess| {
+            let mut table = Table::new();
+            table.insert("day".to_string(), Value::Integer(guess.day.into()));
+            table.insert("part".to_string(), Value::Integer(guess.part.into()));
+            table.insert("answer".to_string(), Value::String(guess.answer.clone()));
+            table.insert(
+                "verdict".to_string(),
+                Value::String(guess.verdict.to_string()),
+            );
+            table.insert("time".to_string(), Value::Integer(guess.time as i64));
+            Value::Table(table)
+        });
+        let mut ledger = Table::new();
+        ledger.insert("guess".to_string(), Value::Array(guesses.collect()));
+        fs::write(&self.path, toml::to_string(&ledger)?)?;
+        Ok(())
+    }
+
+    /// Why `answer` shouldn't be submitted, if it shouldn't: the part is
+    /// solved, it's been wrong before, or it's outside the bounds that "too
+    /// high" and "too low" have set.
+    pub fn refusal(&self, day: u32, part: u32, answer: &str) -> Option<String> {
+        let guesses = || {
+            self.guesses
+                .iter()
+                .filter(move |guess| guess.day == day && guess.part == part)
+        };
+        if let Some(correct) = guesses().find(|guess| guess.verdict == Verdict::Correct) {
+            return Some(format!("part {part} was solved with {}", correct.answer));
+        }
+        if let Some(wrong) =
+            guesses().find(|guess| guess.verdict.is_wrong() && guess.answer == answer)
+        {
+            return Some(format!("{answer} was already {}", wrong.verdict));
+        }
+
+        let answer: i128 = answer.parse().ok()?;
+        let bound = |verdict| {
+            guesses()
+                .filter(move |guess| guess.verdict == verdict)
+                .filter_map(|guess| guess.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Verdict::TooHigh).min().filter(|&high| answer >= high) {
+            return Some(format!("{high} was already too high"));
+        }
+        if let Some(low) = bound(Verdict::TooLow).max().filter(|&low| answer <= low) {
+            return Some(format!("{low} was already too low"));
+        }
+        None
+    }
+
+    pub fn record(&mut self, guess: Guess) {
+        self.guesses.push(guess);
+    }
+}
+
+/// Adds a correct answer to `<year>/answers.toml`, which `aoc run` and the
+/// day binaries check against.
+fn record_answer(
+    root: &Path,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut answers = run::read_answers(root, year)?;
+    let parts = answers
+        .entry(format!("{day:02}"))
+        .or_insert_with(|| Value::Table(Table::new()));
+    let parts = parts
+        .as_table_mut()
+        .ok_or("answers.toml has a day that isn't a table")?;
+    parts.insert(format!("part{part}"), Value::String(answer.to_string()));
+    fs::write(run::answers_path(root, year), toml::to_string(&answers)?)?;
+    Ok(())
+}
+
+/// `aoc submit <year> <day> <part> <answer>`: submits an answer unless the
+/// ledger shows it can't be right, then records the verdict.
+pub fn submit(
+    args: impl IntoIterator<Item = String>,
+    root: &Path,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let args: Vec<String> = args.into_iter().collect();
+    let (year, day, part, answer) = match &args[..] {
+        [year, day, part, answer] => (
+            year.parse().ok(),
+            day.parse().ok(),
+            part.parse().ok(),
+            answer.trim(),
+        ),
+        _ => (None, None, None, ""),
+    };
+    let (Some(year), Some(day), Some(part @ 1..=2)) = (year, day, part) else {
+        eprintln!("{USAGE}");
+        return Ok(ExitCode::from(2));
+    };
+    if !(1..=input::last_day(year)).contains(&day) {
+        eprintln!("{year} has days 1 to {}", input::last_day(year));
+        return Ok(ExitCode::from(2));
+    }
+
+    if answer.is_empty() {
+        eprintln!("{USAGE}");
+        return Ok(ExitCode::from(2));
+    }
+
+    submit_answer(root, year, day, part, answer, || {
+        Client::new(&Config::load()?)?.answer(year, day, part, answer)
+    })
+}
+
+/// Sends an answer with `post`, which returns the page the site replies with,
+/// unless the ledger shows it can't be right. Records the verdict in the
+/// ledger and a correct answer in `answers.toml`.
+fn submit_answer(
+    root: &Path,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+    post: impl FnOnce() -> Result<String, Box<dyn Error>>,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let mut ledger = Ledger::load(Ledger::path(root, year))?;
+    if let Some(reason) = ledger.refusal(day, part, answer) {
+        eprintln!("Not submitting {answer}: {reason}");
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let page = post()?;
+    let reply = puzzle::between(&page, "<article>", "</article>")
+        .first()
+        .map_or_else(|| puzzle::text(&page), |article| puzzle::text(article));
+    let reply = reply.split_whitespace().collect::<Vec<_>>().join(" ");
+    let Some(verdict) = Verdict::parse_reply(&reply) else {
+        return Err(format!("couldn't tell what the site made of {answer}: {reply}").into());
+    };
+
+    let time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let answer = answer.to_string();
+    ledger.record(Guess {
+        day,
+        part,
+        answer: answer.clone(),
+        verdict,
+        time,
+    });
+    ledger.save()?;
+    println!("{reply}");
+    if verdict != Verdict::Correct {
+        return Ok(ExitCode::FAILURE);
+    }
+    record_answer(root, year, day, part, &answer)?;
+    println!(
+        "Recorded {answer} in {}",
+        run::answers_path(root, year).display()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock;
+
+    fn ledger(guesses: &[(u32, &str, Verdict)]) -> Ledger {
+        let path = std::env::temp_dir().join(format!("aoc-guesses-{}.toml", std::process::id()));
+        let guesses = guesses.iter().map(|&(part, answer, verdict)| Guess {
+            day: 9,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            time: 1_733_029_200,
+        });
+        Ledger {
+            path,
+            guesses: guesses.collect(),
+        }
+    }
+
+    #[test]
+    fn verdicts() {
+        let reply = |text| Verdict::parse_reply(text);
+        assert_eq!(
+            reply("That's the right answer! You are one gold star closer"),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            reply("That's not the right answer; your answer is too high. If you're stuck"),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            reply("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            reply("That's not the right answer. If you're stuck, make sure"),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            reply("You gave an answer too recently; you have to wait. You have 37s left to wait."),
+            Some(Verdict::Wait)
+        );
+        assert_eq!(
+            reply("You don't seem to be solving the right level. Did you already complete it?"),
+            Some(Verdict::WrongLevel)
+        );
+        assert_eq!(reply("Something else"), None);
+    }
+
+    #[test]
+    fn refuses_what_cant_be_right() {
+        let ledger = ledger(&[
+            (1, "500", Verdict::TooHigh),
+            (1, "100", Verdict::TooLow),
+            (1, "300", Verdict::Wrong),
+            (1, "200", Verdict::Wait),
+            (2, "abc", Verdict::Correct),
+        ]);
+        assert_eq!(
+            ledger.refusal(9, 1, "300").unwrap(),
+            "300 was already wrong"
+        );
+        assert_eq!(
+            ledger.refusal(9, 1, "500").unwrap(),
+            "500 was already too high"
+        );
+        assert_eq!(
+            ledger.refusal(9, 1, "600").unwrap(),
+            "500 was already too high"
+        );
+        assert_eq!(
+            ledger.refusal(9, 1, "99").unwrap(),
+            "100 was already too low"
+        );
+        assert_eq!(ledger.refusal(9, 1, "200"), None);
+        assert_eq!(ledger.refusal(9, 1, "x"), None);
+        assert_eq!(ledger.refusal(10, 1, "600"), None);
+        assert_eq!(
+            ledger.refusal(9, 2, "def").unwrap(),
+            "part 2 was solved with abc"
+        );
+    }
+
+    #[test]
+    fn ledger_round_trip() {
+        let saved = ledger(&[(1, "12", Verdict::TooLow), (2, "x\"y", Verdict::WrongLevel)]);
+        saved.save().unwrap();
+        let loaded = Ledger::load(saved.path.clone()).unwrap();
+        fs::remove_file(&saved.path).unwrap();
+        assert_eq!(loaded.guesses, saved.guesses);
+    }
+
+    #[test]
+    fn submits_and_records() {
+        let root = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("2024")).unwrap();
+
+        let (url, server) = mock::serve(vec![
+            (
+                200,
+                "<main><article><p>That's the right answer!</p></article></main>",
+            ),
+            (
+                200,
+                "<article><p>That's not the right answer; your answer is too high.</p></article>",
+            ),
+        ]);
+        let mut client = mock::client(url);
+        let post = |client: &mut Client, part, answer| client.answer(2024, 9, part, answer);
+        let code = submit_answer(&root, 2024, 9, 1, "1928", || post(&mut client, 1, "1928"));
+        assert_eq!(code.unwrap(), ExitCode::SUCCESS);
+        let code = submit_answer(&root, 2024, 9, 2, "6000", || post(&mut client, 2, "6000"));
+        assert_eq!(code.unwrap(), ExitCode::FAILURE);
+        let unreachable = || -> Result<String, Box<dyn Error>> { panic!("resubmitted") };
+        let code = submit_answer(&root, 2024, 9, 2, "6001", unreachable);
+        assert_eq!(code.unwrap(), ExitCode::FAILURE);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=1928"));
+        assert!(requests[1].ends_with("\r\n\r\nlevel=2&answer=6000"));
+
+        let ledger = Ledger::load(Ledger::path(&root, 2024)).unwrap();
+        let verdicts: Vec<_> = ledger
+            .guesses
+            .iter()
+            .map(|guess| (guess.part, guess.answer.as_str(), guess.verdict))
+            .collect();
+        assert_eq!(
+            verdicts,
+            [(1, "1928", Verdict::Correct), (2, "6000", Verdict::TooHigh)]
+        );
+        let answers = run::read_answers(&root, 2024).unwrap();
+        assert_eq!(answers["09"]["part1"].as_str(), Some("1928"));
+        assert_eq!(answers["09"].get("part2"), None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,30 +1,873 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{parse_macro_input, Ident, ItemFn, LitInt};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    spanned::Spanned,
+    Expr, FnArg, GenericArgument, Ident, ItemFn, LitInt, LitStr, PathArguments, ReturnType, Token,
+    Type,
+};
 
+/// Turns `fn main(input: &str) -> (impl Display, impl Display)` into a binary
+/// that times both parts. The function may also return
+/// `Result<(impl Display, impl Display), E>`, in which case an error is
+/// printed along with its sources and the binary exits with status 1.
+///
+/// The puzzle input is `inputs/DD.txt`, read at run time or, with the
+/// `embed-inputs` feature, embedded at compile time. Either way the binary can
+/// be given `--input <path>` (`-` reads stdin) or `--example [n]` instead,
+/// which reads `inputs/DD.example` for the first example and
+/// `inputs/DD.example.<n>` for the others, as saved by `aoc examples`.
+///
+/// `--bench [runs]` (100 by default) warms up, then times `runs` calls, each
+/// on a fresh copy of the input, and reports the min, median, mean and
+/// standard deviation in nanoseconds.
+///
+/// `--format json` prints a single line with the year, day, answers and
+/// timings in nanoseconds instead, using the medians when benchmarking.
+/// Solutions should write any debug output to stderr.
+///
+/// Answers for the puzzle input are checked against `<year>/answers.toml` and
+/// reported as PASS, FAIL or UNKNOWN. `--record` stores them there once
+/// they've been accepted. The crate using these attributes needs `toml` as a
+/// dependency to read that file.
+///
+/// The day can be followed by an example and its answers, which become a
+/// test named `aoc_example`. The example is any `&str` expression, or a file
+/// relative to the year's directory, and `_` skips a part:
+///
+/// ```ignore
+/// #[aoc::main(09, example = "0 3 6 9 12 15", expect = (18, -3))]
+/// #[aoc::main(05, example_file = "inputs/05.example", expect = (35, _))]
+/// ```
 #[proc_macro_attribute]
 pub fn main(args: TokenStream, input: TokenStream) -> TokenStream {
-    let day = parse_macro_input!(args as LitInt);
-    let input_path = format!("../../../inputs/{}.txt", day.token());
+    let DayArgs { day, example } = parse_macro_input!(args as DayArgs);
 
     let mut aoc_solution = parse_macro_input!(input as ItemFn);
     aoc_solution.sig.ident = Ident::new("aoc_solution", aoc_solution.sig.ident.span());
 
+    let runtime = match Layout::bin(&day) {
+        Ok(layout) => runtime(&layout),
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let example = example.map(|example| {
+        example.test(quote! {
+          let (part1, part2) = AocAnswer::into_answer(aoc_solution(input))
+            .unwrap_or_else(|e| panic!("{}", e));
+        })
+    });
     let tokens = quote! {
-      const INPUT: &str = include_str!(#input_path);
       #aoc_solution
+      #runtime
+      #example
+      trait AocAnswer {
+        fn into_answer(self) -> ::std::result::Result<(String, String), Box<dyn ::std::error::Error>>;
+      }
+      impl<A: ::std::fmt::Display, B: ::std::fmt::Display> AocAnswer for (A, B) {
+        fn into_answer(self) -> ::std::result::Result<(String, String), Box<dyn ::std::error::Error>> {
+          Ok((self.0.to_string(), self.1.to_string()))
+        }
+      }
+      impl<A, B, E> AocAnswer for ::std::result::Result<(A, B), E>
+      where
+        A: ::std::fmt::Display,
+        B: ::std::fmt::Display,
+        E: Into<Box<dyn ::std::error::Error>>,
+      {
+        fn into_answer(self) -> ::std::result::Result<(String, String), Box<dyn ::std::error::Error>> {
+          self.map_err(Into::into)?.into_answer()
+        }
+      }
       fn main() {
+        let AocArgs { input, bench, json, puzzle_input, record } = aoc_args();
+        if let Some(runs) = bench {
+          let (answer, stats) = aoc_bench(
+            runs,
+            || input.trim_end().to_owned(),
+            |input| aoc_solution(input),
+          );
+          let (p1, p2) = AocAnswer::into_answer(answer).unwrap_or_else(|e| aoc_fail(e));
+          let check = puzzle_input.then(|| aoc_check(&p1, &p2, record));
+          if json {
+            aoc_json(&p1, &p2, None, None, None, stats.median as u128);
+            return;
+          }
+          println!("Part one: {}", p1);
+          println!("Part two: {}", p2);
+          if let Some(check) = check {
+            println!("{}", check);
+          }
+          println!("Bench ({} runs): {}", runs, stats);
+          return;
+        }
         let now = ::std::time::Instant::now();
-        let (p1, p2) = aoc_solution(INPUT.trim_end());
+        let answer = aoc_solution(input.trim_end());
         let elapsed = now.elapsed();
+        let (p1, p2) = AocAnswer::into_answer(answer).unwrap_or_else(|e| aoc_fail(e));
+        let check = puzzle_input.then(|| aoc_check(&p1, &p2, record));
+        if json {
+          aoc_json(&p1, &p2, None, None, None, elapsed.as_nanos());
+          return;
+        }
         println!("Part one: {}", p1);
         println!("Part two: {}", p2);
-        if elapsed.as_millis() > 0 {
-          println!("Time: {}ms", elapsed.as_millis());
-        } else {
-          println!("Time: {}μs", elapsed.as_micros());
+        if let Some(check) = check {
+          println!("{}", check);
+        }
+        println!("Time: {}", aoc_time(elapsed));
+      }
+    };
+    TokenStream::from(tokens)
+}
+
+/// Marks `fn(&str) -> T` as the parser for [`macro@part1`] and
+/// [`macro@part2`], which each take `&T`, and generates a `main` that times
+/// the three separately. Any of them may return a `Result` instead.
+///
+/// Inputs, flags and examples work the same way as for [`macro@main`], except
+/// that the parser and each part are timed separately.
+#[proc_macro_attribute]
+pub fn parse(args: TokenStream, input: TokenStream) -> TokenStream {
+    let DayArgs { day, example } = parse_macro_input!(args as DayArgs);
+    let parse = parse_macro_input!(input as ItemFn);
+    let day_number = match day.base10_parse::<u32>() {
+        Ok(n) => n,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let name = &parse.sig.ident;
+    let (parsed, fallible) = match &parse.sig.output {
+        ReturnType::Type(_, ty) => unwrap_result(ty),
+        ReturnType::Default => {
+            let msg = "#[aoc::parse] functions must return the parsed input";
+            return syn::Error::new(parse.sig.span(), msg)
+                .to_compile_error()
+                .into();
+        }
+    };
+    let call = if fallible {
+        quote! { #name(input).map_err(Into::into) }
+    } else {
+        quote! { Ok(#name(input)) }
+    };
+
+    let runtime = match Layout::bin(&day) {
+        Ok(layout) => runtime(&layout),
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let example = example.map(|example| {
+        let part = |expected: &Option<Expr>, part: TokenStream2| match expected {
+            Some(_) => quote! { #part(&parsed).unwrap_or_else(|e| panic!("{}", e)) },
+            None => quote! { String::new() },
+        };
+        let part1 = part(&example.part1, quote! { aoc_part1 });
+        let part2 = part(&example.part2, quote! { aoc_part2 });
+        example.test(quote! {
+          let parsed = aoc_parse(input).unwrap_or_else(|e| panic!("{}", e));
+          let (part1, part2) = (#part1, #part2);
+        })
+    });
+    let tokens = quote! {
+      #parse
+      #example
+      const AOC_DAY: u32 = #day_number;
+      fn aoc_parse(input: &str) -> ::std::result::Result<#parsed, Box<dyn ::std::error::Error>> {
+        #call
+      }
+      #runtime
+      fn main() {
+        let AocArgs { input, bench, json, puzzle_input, record } = aoc_args();
+        if let Some(runs) = bench {
+          let (parsed, parse) = aoc_bench(
+            runs,
+            || input.trim_end().to_owned(),
+            |input| aoc_parse(input),
+          );
+          let parsed = parsed.unwrap_or_else(|e| aoc_fail(e));
+          let (p1, part1) = aoc_bench(runs, || (), |_| aoc_part1(&parsed));
+          let (p2, part2) = aoc_bench(runs, || (), |_| aoc_part2(&parsed));
+          let p1 = p1.unwrap_or_else(|e| aoc_fail(e));
+          let p2 = p2.unwrap_or_else(|e| aoc_fail(e));
+          let check = puzzle_input.then(|| aoc_check(&p1, &p2, record));
+          if json {
+            let [parse, part1, part2] = [parse, part1, part2].map(|stats| stats.median as u128);
+            aoc_json(&p1, &p2, Some(parse), Some(part1), Some(part2), parse + part1 + part2);
+            return;
+          }
+          println!("Part one: {}", p1);
+          println!("Part two: {}", p2);
+          if let Some(check) = check {
+            println!("{}", check);
+          }
+          println!("Bench ({} runs):", runs);
+          println!("  parse: {}", parse);
+          println!("  part one: {}", part1);
+          println!("  part two: {}", part2);
+          return;
+        }
+        let now = ::std::time::Instant::now();
+        let parsed = aoc_parse(input.trim_end()).unwrap_or_else(|e| aoc_fail(e));
+        let parse_time = now.elapsed();
+        let now = ::std::time::Instant::now();
+        let p1 = aoc_part1(&parsed).unwrap_or_else(|e| aoc_fail(e));
+        let p1_time = now.elapsed();
+        let now = ::std::time::Instant::now();
+        let p2 = aoc_part2(&parsed).unwrap_or_else(|e| aoc_fail(e));
+        let p2_time = now.elapsed();
+        let check = puzzle_input.then(|| aoc_check(&p1, &p2, record));
+        if json {
+          let [parse, part1, part2] = [parse_time, p1_time, p2_time].map(|t| t.as_nanos());
+          aoc_json(&p1, &p2, Some(parse), Some(part1), Some(part2), parse + part1 + part2);
+          return;
+        }
+        println!("Parse: {}", aoc_time(parse_time));
+        println!("Part one: {} ({})", p1, aoc_time(p1_time));
+        println!("Part two: {} ({})", p2, aoc_time(p2_time));
+        if let Some(check) = check {
+          println!("{}", check);
         }
+        println!("Time: {}", aoc_time(parse_time + p1_time + p2_time));
       }
     };
     TokenStream::from(tokens)
 }
+
+/// Marks the solution to part one. See [`macro@parse`].
+#[proc_macro_attribute]
+pub fn part1(args: TokenStream, input: TokenStream) -> TokenStream {
+    part(args, input, "aoc_part1")
+}
+
+/// Marks the solution to part two. See [`macro@parse`].
+#[proc_macro_attribute]
+pub fn part2(args: TokenStream, input: TokenStream) -> TokenStream {
+    part(args, input, "aoc_part2")
+}
+
+/// Generates a `main` that runs one day of an aoc-runner library, chosen by
+/// the first argument, with the same flags, output and timing as
+/// [`macro@main`]:
+///
+/// ```ignore
+/// aoc_macros::runner_main!(lib = aoc);
+/// ```
+///
+/// The days come from the `target/aoc/completed.json` that `aoc_lib!` writes,
+/// and each reads `input/<year>/day<N>.txt`. Unnamed solutions are preferred
+/// over named ones. The generators of both parts count as the parse time.
+#[proc_macro]
+pub fn runner_main(input: TokenStream) -> TokenStream {
+    let RunnerArgs { lib } = parse_macro_input!(input as RunnerArgs);
+    let parts = match aoc_runner_internal::DayParts::load() {
+        Ok(parts) => parts,
+        Err(e) => {
+            let msg = format!("failed to read target/aoc/completed.json from aoc_lib!: {e}");
+            return syn::Error::new(lib.span(), msg).to_compile_error().into();
+        }
+    };
+
+    // Parts are sorted by day, part and then name, so the unnamed one is first.
+    let mut days = std::collections::BTreeMap::<u32, [Option<Ident>; 2]>::new();
+    for part in parts.iter() {
+        let day = u32::from(part.day.0);
+        let factory = match &part.name {
+            Some(name) => format!("day{day}_part{}_{}", part.part.0, name.to_lowercase()),
+            None => format!("day{day}_part{}", part.part.0),
+        };
+        let slot = &mut days.entry(day).or_default()[usize::from(part.part.0) - 1];
+        slot.get_or_insert_with(|| Ident::new(&factory, proc_macro2::Span::call_site()));
+    }
+
+    let modules = days.iter().map(|(&day, [part1, part2])| {
+        let module = Ident::new(&format!("aoc_day{day}"), proc_macro2::Span::call_site());
+        let runtime = runtime(&Layout::runner(parts.year, day));
+        let [part1, part2] = [part1, part2].map(|part| match part {
+            Some(factory) => quote! { Some(Factory::#factory) },
+            None => quote! { None },
+        });
+        quote! {
+          mod #module {
+            use #lib::*;
+            #runtime
+            pub fn main() {
+              use ::aoc_runner::Runner as _;
+              type AocPart = fn(
+                ::aoc_runner::ArcStr,
+              ) -> ::std::result::Result<Box<dyn ::aoc_runner::Runner>, Box<dyn ::std::error::Error>>;
+              let AocArgs { input, bench, json, puzzle_input, record } = aoc_args();
+              let input = ::aoc_runner::ArcStr::from(input.trim_end());
+              let parts: [Option<AocPart>; 2] = [#part1, #part2];
+              let (mut answers, mut parse_ns, mut part_ns) = ([String::new(), String::new()], 0, [None, None]);
+              let mut stats = Vec::new();
+              for (i, part) in parts.into_iter().enumerate() {
+                let Some(part) = part else { continue };
+                let answer = if let Some(runs) = bench {
+                  let (runner, generate) = aoc_bench(runs, || input.clone(), |input| part(input.clone()));
+                  let runner = runner.unwrap_or_else(|e| aoc_fail(e));
+                  let (answer, run) = aoc_bench(runs, || (), |_| runner.try_run());
+                  parse_ns += generate.median as u128;
+                  part_ns[i] = Some(run.median as u128);
+                  stats.push((["one", "two"][i], generate, run));
+                  answer
+                } else {
+                  let now = ::std::time::Instant::now();
+                  let runner = part(input.clone()).unwrap_or_else(|e| aoc_fail(e));
+                  parse_ns += now.elapsed().as_nanos();
+                  let now = ::std::time::Instant::now();
+                  let answer = runner.try_run();
+                  part_ns[i] = Some(now.elapsed().as_nanos());
+                  answer
+                };
+                answers[i] = answer.unwrap_or_else(|e| aoc_fail(e)).to_string();
+              }
+              let [p1, p2] = &answers;
+              let total_ns = parse_ns + part_ns.iter().flatten().sum::<u128>();
+              let check = puzzle_input.then(|| aoc_check(p1, p2, record));
+              if json {
+                aoc_json(p1, p2, Some(parse_ns), part_ns[0], part_ns[1], total_ns);
+                return;
+              }
+              let time = |ns: u128| aoc_time(::std::time::Duration::from_nanos(ns as u64));
+              if bench.is_none() {
+                println!("Parse: {}", time(parse_ns));
+              }
+              for (name, answer, ns) in [("one", p1, part_ns[0]), ("two", p2, part_ns[1])] {
+                match ns {
+                  Some(ns) if bench.is_none() => println!("Part {}: {} ({})", name, answer, time(ns)),
+                  Some(_) => println!("Part {}: {}", name, answer),
+                  None => println!("Part {}: unsolved", name),
+                }
+              }
+              if let Some(check) = check {
+                println!("{}", check);
+              }
+              match bench {
+                Some(runs) => {
+                  println!("Bench ({} runs):", runs);
+                  for (name, generate, run) in stats {
+                    println!("  generate {}: {}", name, generate);
+                    println!("  part {}: {}", name, run);
+                  }
+                }
+                None => println!("Time: {}", time(total_ns)),
+              }
+            }
+          }
+        }
+    });
+    let arms = days.keys().map(|&day| {
+        let module = Ident::new(&format!("aoc_day{day}"), proc_macro2::Span::call_site());
+        quote! { Some(#day) => #module::main(), }
+    });
+    let days = days
+        .keys()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let usage = format!("usage: {{}} <day> [flags], where day is one of {days}");
+
+    let tokens = quote! {
+      #(#modules)*
+      fn main() {
+        match ::std::env::args().nth(1).and_then(|day| day.parse::<u32>().ok()) {
+          #(#arms)*
+          _ => {
+            eprintln!(#usage, env!("CARGO_BIN_NAME"));
+            ::std::process::exit(2)
+          }
+        }
+      }
+    };
+    TokenStream::from(tokens)
+}
+
+/// Arguments to [`runner_main!`]: `lib = <crate>`, like `aoc_main!`.
+struct RunnerArgs {
+    lib: Ident,
+}
+
+impl Parse for RunnerArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        if key != "lib" {
+            return Err(syn::Error::new(key.span(), "expected `lib = <crate>`"));
+        }
+        input.parse::<Token![=]>()?;
+        let lib = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(RunnerArgs { lib })
+    }
+}
+
+/// Wraps a part in `fn #wrapper(&Parsed) -> Result<String, _>` for the `main`
+/// generated by `#[aoc::parse]`.
+fn part(args: TokenStream, input: TokenStream, wrapper: &str) -> TokenStream {
+    let day = parse_macro_input!(args as LitInt);
+    let part = parse_macro_input!(input as ItemFn);
+    let day_number = match day.base10_parse::<u32>() {
+        Ok(n) => n,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let parsed = match part.sig.inputs.first() {
+        Some(FnArg::Typed(arg)) if part.sig.inputs.len() == 1 => &arg.ty,
+        _ => {
+            let msg = "parts must take a single argument: a reference to the parsed input";
+            return syn::Error::new(part.sig.inputs.span(), msg)
+                .to_compile_error()
+                .into();
+        }
+    };
+    let fallible = match &part.sig.output {
+        ReturnType::Type(_, ty) => unwrap_result(ty).1,
+        ReturnType::Default => false,
+    };
+
+    let name = &part.sig.ident;
+    let wrapper = Ident::new(wrapper, name.span());
+    let answer = if fallible {
+        quote! { #name(parsed).map_err(Into::<Box<dyn ::std::error::Error>>::into)? }
+    } else {
+        quote! { #name(parsed) }
+    };
+
+    let tokens = quote! {
+      #part
+      const _: () = assert!(AOC_DAY == #day_number, "part is for a different day than #[aoc::parse]");
+      fn #wrapper(parsed: #parsed) -> ::std::result::Result<String, Box<dyn ::std::error::Error>> {
+        Ok(#answer.to_string())
+      }
+    };
+    TokenStream::from(tokens)
+}
+
+/// Arguments to [`macro@main`] and [`macro@parse`].
+struct DayArgs {
+    day: LitInt,
+    example: Option<Example>,
+}
+
+/// An example input and the answers expected for it, `None` for parts that
+/// shouldn't be checked.
+struct Example {
+    input: TokenStream2,
+    part1: Option<Expr>,
+    part2: Option<Expr>,
+}
+
+impl Parse for DayArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let day: LitInt = input.parse()?;
+        let (mut example, mut expect) = (None, None);
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "example" if example.is_none() => {
+                    let text: Expr = input.parse()?;
+                    example = Some(quote! { #text });
+                }
+                "example_file" if example.is_none() => {
+                    let path: LitStr = input.parse()?;
+                    let message = format!("failed to read {}", path.value());
+                    example = Some(quote! {
+                      &::std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../", #path))
+                        .expect(#message)
+                    });
+                }
+                "expect" if expect.is_none() => {
+                    let tuple: syn::ExprTuple = input.parse()?;
+                    if tuple.elems.len() != 2 {
+                        let msg = "expected the answers to both parts, e.g. `(35, _)`";
+                        return Err(syn::Error::new(tuple.span(), msg));
+                    }
+                    let mut answers = tuple.elems.into_iter().map(|answer| match answer {
+                        Expr::Infer(_) => None,
+                        answer => Some(answer),
+                    });
+                    expect = Some((key, answers.next().unwrap(), answers.next().unwrap()));
+                }
+                _ => {
+                    let msg = "expected one each of `example` or `example_file`, and `expect`";
+                    return Err(syn::Error::new(key.span(), msg));
+                }
+            }
+        }
+
+        let example = match (example, expect) {
+            (None, None) => None,
+            (Some(input), Some((_, part1, part2))) => Some(Example {
+                input,
+                part1,
+                part2,
+            }),
+            (Some(_), None) => {
+                let msg = "an example needs `expect = (part1, part2)`";
+                return Err(syn::Error::new(day.span(), msg));
+            }
+            (None, Some((key, ..))) => {
+                let msg = "`expect` needs an `example` or `example_file`";
+                return Err(syn::Error::new(key.span(), msg));
+            }
+        };
+        Ok(DayArgs { day, example })
+    }
+}
+
+impl Example {
+    /// The `aoc_example` test. `solve` has `input` in scope and must define
+    /// `part1` and `part2` as strings.
+    fn test(&self, solve: TokenStream2) -> TokenStream2 {
+        let input = &self.input;
+        let check = |part: &str, expected: &Option<Expr>| {
+            let answer = Ident::new(part, proc_macro2::Span::call_site());
+            expected.as_ref().map(|expected| {
+                quote! { assert_eq!(#answer, (#expected).to_string(), "wrong answer to {}", #part); }
+            })
+        };
+        let part1 = check("part1", &self.part1);
+        let part2 = check("part2", &self.part2);
+        quote! {
+          #[cfg(test)]
+          #[test]
+          #[allow(unused_variables)]
+          fn aoc_example() {
+            let input: &str = #input;
+            let input = input.trim_end();
+            #solve
+            #part1
+            #part2
+          }
+        }
+    }
+}
+
+/// Returns `T` and `true` for `Result<T, ..>` (or any other `..::Result<T>`),
+/// and `ty` itself and `false` for anything else.
+fn unwrap_result(ty: &Type) -> (&Type, bool) {
+    if let Type::Path(path) = ty {
+        if let Some(last) = path.path.segments.last() {
+            if let (true, PathArguments::AngleBracketed(args)) =
+                (last.ident == "Result", &last.arguments)
+            {
+                if let Some(GenericArgument::Type(ok)) = args.args.first() {
+                    return (ok, true);
+                }
+            }
+        }
+    }
+    (ty, false)
+}
+
+/// Where a day's inputs are and how its binary is invoked.
+struct Layout {
+    day: u32,
+    /// The puzzle input, relative to the crate's directory.
+    input: String,
+    /// The first example, relative to the crate's directory. Later examples
+    /// append `.<n>`.
+    example: String,
+    /// How many arguments come before the flags.
+    leading_args: usize,
+    /// The command line shown in the usage message.
+    program: TokenStream2,
+}
+
+impl Layout {
+    /// A `#[aoc::main]` or `#[aoc::parse]` binary, which reads `inputs/DD.txt`.
+    fn bin(day: &LitInt) -> syn::Result<Self> {
+        let day = day.base10_parse::<u32>()?;
+        Ok(Layout {
+            day,
+            input: format!("../inputs/{day:02}.txt"),
+            example: format!("../inputs/{day:02}.example"),
+            leading_args: 1,
+            program: quote! { env!("CARGO_BIN_NAME") },
+        })
+    }
+
+    /// A day run by [`runner_main!`], which reads aoc-runner's
+    /// `input/YYYY/dayN.txt`.
+    fn runner(year: u32, day: u32) -> Self {
+        let program = format!(" {day}");
+        Layout {
+            day,
+            input: format!("input/{year}/day{day}.txt"),
+            example: format!("input/{year}/day{day}.example"),
+            leading_args: 2,
+            program: quote! { concat!(env!("CARGO_BIN_NAME"), #program) },
+        }
+    }
+}
+
+/// The year of the crate being compiled. Each year's crate is in `<year>/rust`.
+fn crate_year() -> Option<u32> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").ok()?;
+    let dir = std::path::Path::new(&dir);
+    dir.parent()?.file_name()?.to_str()?.parse().ok()
+}
+
+/// Items shared by every generated `main`: parsing the arguments, reading the
+/// input, reporting errors, and timing or benchmarking the solution.
+fn runtime(layout: &Layout) -> TokenStream2 {
+    let Layout {
+        day: day_number,
+        input,
+        example,
+        leading_args,
+        program,
+    } = layout;
+    let year = crate_year().map_or_else(|| "null".to_string(), |year| year.to_string());
+
+    let input_path = format!("/{input}");
+    let example_path = format!("/{example}");
+
+    let default_input = if cfg!(feature = "embed-inputs") {
+        quote! {
+          ::std::borrow::Cow::Borrowed(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), #input_path)))
+        }
+    } else {
+        quote! { read(&format!("{}{}", env!("CARGO_MANIFEST_DIR"), #input_path)) }
+    };
+
+    quote! {
+      struct AocArgs {
+        input: ::std::borrow::Cow<'static, str>,
+        /// Number of timed runs requested with `--bench`.
+        bench: Option<usize>,
+        /// Whether `--format json` was given.
+        json: bool,
+        /// Whether the input is the puzzle input, which `answers.toml` is for.
+        puzzle_input: bool,
+        /// Whether `--record` was given.
+        record: bool,
+      }
+      fn aoc_args() -> AocArgs {
+        fn usage() -> ! {
+          eprintln!(
+            "usage: {} [--input <path|-> | --example [n]] [--bench [runs]] [--format text|json] [--record]",
+            #program,
+          );
+          ::std::process::exit(2)
+        }
+        fn read(path: &str) -> ::std::borrow::Cow<'static, str> {
+          let contents = if path == "-" {
+            ::std::io::read_to_string(::std::io::stdin())
+          } else {
+            ::std::fs::read_to_string(path)
+          };
+          match contents {
+            Ok(contents) => ::std::borrow::Cow::Owned(contents),
+            Err(e) => {
+              eprintln!("failed to read {}: {}", path, e);
+              ::std::process::exit(1)
+            }
+          }
+        }
+        type Args = ::std::iter::Peekable<::std::iter::Skip<::std::env::Args>>;
+        fn count(args: &mut Args) -> Option<String> {
+          args.next_if(|n| n.parse::<usize>().is_ok_and(|n| n > 0))
+        }
+        let mut args = ::std::env::args().skip(#leading_args).peekable();
+        let (mut path, mut bench, mut json, mut record) = (None, None, None, false);
+        while let Some(arg) = args.next() {
+          match arg.as_str() {
+            "--input" if path.is_none() => path = Some(args.next().unwrap_or_else(|| usage())),
+            "--example" if path.is_none() => {
+              let suffix = count(&mut args).filter(|n| n.as_str() != "1").map(|n| format!(".{n}")).unwrap_or_default();
+              path = Some(format!("{}{}{}", env!("CARGO_MANIFEST_DIR"), #example_path, suffix));
+            }
+            "--bench" if bench.is_none() => {
+              bench = Some(count(&mut args).map_or(100, |n| n.parse().unwrap()));
+            }
+            "--format" if json.is_none() => match args.next().as_deref() {
+              Some("text") => json = Some(false),
+              Some("json") => json = Some(true),
+              _ => usage(),
+            },
+            "--record" if !record => record = true,
+            _ => usage(),
+          }
+        }
+        if record && path.is_some() {
+          eprintln!("--record only applies to the puzzle input");
+          usage();
+        }
+        let puzzle_input = path.is_none();
+        let input = match path {
+          Some(path) => read(&path),
+          None => #default_input,
+        };
+        AocArgs {
+          input,
+          bench,
+          json: json.unwrap_or(false),
+          puzzle_input,
+          record,
+        }
+      }
+      const AOC_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+      /// Answers from `<year>/answers.toml`, keyed by zero-padded day and then
+      /// by part, as `aoc run` and `aoc submit` read and write them.
+      fn aoc_read_answers() -> ::toml::Table {
+        let answers = match ::std::fs::read_to_string(AOC_ANSWERS) {
+          Ok(contents) => contents.parse().map_err(Into::into),
+          Err(e) if e.kind() == ::std::io::ErrorKind::NotFound => Ok(::toml::Table::new()),
+          Err(e) => Err(Box::<dyn ::std::error::Error>::from(e)),
+        };
+        answers.unwrap_or_else(|e| {
+          eprintln!("failed to read {}: {}", AOC_ANSWERS, e);
+          ::std::process::exit(1)
+        })
+      }
+      /// Compares the answers with the recorded ones, then records them if
+      /// `record` is set, replacing any earlier answers for this day.
+      fn aoc_check(part1: &str, part2: &str, record: bool) -> String {
+        let day = format!("{:02}", #day_number);
+        let mut answers = aoc_read_answers();
+        let recorded = answers.get(&day);
+        let status = |part: &str, answer: &str| {
+          let expected = match recorded.and_then(|parts| parts.get(part)) {
+            Some(::toml::Value::String(expected)) => expected.clone(),
+            Some(::toml::Value::Integer(expected)) => expected.to_string(),
+            _ => return "UNKNOWN".to_string(),
+          };
+          if expected == answer {
+            "PASS".to_string()
+          } else {
+            format!("FAIL (expected {})", expected)
+          }
+        };
+        let check = format!(
+          "Check: part one {}, part two {}",
+          status("part1", part1),
+          status("part2", part2),
+        );
+        if !record {
+          return check;
+        }
+        let mut parts = ::toml::Table::new();
+        parts.insert("part1".to_string(), ::toml::Value::String(part1.to_string()));
+        parts.insert("part2".to_string(), ::toml::Value::String(part2.to_string()));
+        answers.insert(day, ::toml::Value::Table(parts));
+        let written = ::toml::to_string(&answers)
+          .map_err(Box::<dyn ::std::error::Error>::from)
+          .and_then(|out| Ok(::std::fs::write(AOC_ANSWERS, out)?));
+        if let Err(e) = written {
+          eprintln!("failed to write {}: {}", AOC_ANSWERS, e);
+          ::std::process::exit(1)
+        }
+        format!("{}\nRecorded answers in {}", check, AOC_ANSWERS)
+      }
+      fn aoc_fail(e: Box<dyn ::std::error::Error>) -> ! {
+        eprintln!("error: {}", e);
+        let mut source = e.source();
+        while let Some(e) = source {
+          eprintln!("caused by: {}", e);
+          source = e.source();
+        }
+        ::std::process::exit(1)
+      }
+      /// Prints the answers and timings as one line of JSON. Timings are `null`
+      /// for stages that weren't timed on their own.
+      fn aoc_json(
+        part1: &str,
+        part2: &str,
+        parse_ns: Option<u128>,
+        part1_ns: Option<u128>,
+        part2_ns: Option<u128>,
+        total_ns: u128,
+      ) {
+        fn string(s: &str) -> String {
+          let mut out = String::from('"');
+          for c in s.chars() {
+            match c {
+              '"' => out.push_str("\\\""),
+              '\\' => out.push_str("\\\\"),
+              c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+              c => out.push(c),
+            }
+          }
+          out.push('"');
+          out
+        }
+        fn number(n: Option<u128>) -> String {
+          n.map_or_else(|| "null".to_string(), |n| n.to_string())
+        }
+        println!(
+          "{{\"year\":{},\"day\":{},\"part1\":{},\"part2\":{},\"parse_ns\":{},\"part1_ns\":{},\"part2_ns\":{},\"total_ns\":{}}}",
+          #year,
+          #day_number,
+          string(part1),
+          string(part2),
+          number(parse_ns),
+          number(part1_ns),
+          number(part2_ns),
+          total_ns,
+        );
+      }
+      fn aoc_time(elapsed: ::std::time::Duration) -> String {
+        if elapsed.as_millis() > 0 {
+          format!("{}ms", elapsed.as_millis())
+        } else {
+          format!("{}μs", elapsed.as_micros())
+        }
+      }
+      /// Runs `run` on a fresh value from `setup` a tenth as many times as
+      /// `runs` to warm up, then `runs` more times while timing only `run`.
+      /// Returns the output of the last run and a summary of the timings.
+      fn aoc_bench<I, T>(
+        runs: usize,
+        mut setup: impl FnMut() -> I,
+        mut run: impl FnMut(&I) -> T,
+      ) -> (T, AocStats) {
+        for _ in 0..runs / 10 + 1 {
+          let input = setup();
+          ::std::hint::black_box(run(::std::hint::black_box(&input)));
+        }
+        let mut samples = Vec::with_capacity(runs);
+        let mut output = None;
+        for _ in 0..runs {
+          let input = setup();
+          let now = ::std::time::Instant::now();
+          let out = ::std::hint::black_box(run(::std::hint::black_box(&input)));
+          samples.push(now.elapsed().as_nanos() as f64);
+          output = Some(out);
+        }
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len() as f64;
+        let median = (samples[(runs - 1) / 2] + samples[runs / 2]) / 2.0;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        let stats = AocStats {
+          min: samples[0],
+          median,
+          mean,
+          stddev: variance.sqrt(),
+        };
+        (output.unwrap(), stats)
+      }
+      /// Timings from [`aoc_bench`], in nanoseconds.
+      struct AocStats {
+        min: f64,
+        median: f64,
+        mean: f64,
+        stddev: f64,
+      }
+      impl ::std::fmt::Display for AocStats {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+          write!(
+            f,
+            "min {:.0}ns, median {:.0}ns, mean {:.0}ns, stddev {:.0}ns",
+            self.min, self.median, self.mean, self.stddev,
+          )
+        }
+      }
+    }
+}
@@ -1,3 +1,5 @@
+use anyhow::Context;
+
 fn p1(mut left: Vec<usize>, mut right: Vec<usize>) -> usize {
     let mut total_distance = 0;
 
@@ -31,21 +33,29 @@ fn p2(left: &[usize], right: &[usize]) -> usize {
     similarity
 }
 
-#[aoc::main(01)]
-fn main(input: &str) -> (usize, usize) {
+fn parse(input: &str) -> anyhow::Result<(Vec<usize>, Vec<usize>)> {
     let mut left = vec![];
     let mut right = vec![];
 
-    input.lines().for_each(|line| {
-        let split = line.split("   ").collect::<Vec<&str>>();
-        left.push(split[0].parse::<usize>().unwrap());
-        right.push(split[1].parse::<usize>().unwrap());
-    });
+    for (i, line) in input.lines().enumerate() {
+        let (l, r) = line
+            .split_once("   ")
+            .with_context(|| format!("line {}: expected two numbers", i + 1))?;
+        left.push(l.parse().with_context(|| format!("line {}", i + 1))?);
+        right.push(r.parse().with_context(|| format!("line {}", i + 1))?);
+    }
+
+    Ok((left, right))
+}
+
+#[aoc::main(01)]
+fn main(input: &str) -> anyhow::Result<(usize, usize)> {
+    let (left, right) = parse(input)?;
 
     let p2 = p2(&left, &right);
     let p1 = p1(left, right);
 
-    (p1, p2)
+    Ok((p1, p2))
 }
 
 #[cfg(test)]
@@ -61,14 +71,7 @@ mod tests {
 
     #[test]
     fn example_p1() {
-        let mut left = vec![];
-        let mut right = vec![];
-
-        EXAMPLE.lines().for_each(|line| {
-            let split = line.split("   ").collect::<Vec<&str>>();
-            left.push(split[0].parse::<usize>().unwrap());
-            right.push(split[1].parse::<usize>().unwrap());
-        });
+        let (left, right) = parse(EXAMPLE).unwrap();
 
         let sum = p1(left, right);
 
@@ -77,17 +80,16 @@ mod tests {
 
     #[test]
     fn example_p2() {
-        let mut left = vec![];
-        let mut right = vec![];
-
-        EXAMPLE.lines().for_each(|line| {
-            let split = line.split("   ").collect::<Vec<&str>>();
-            left.push(split[0].parse::<usize>().unwrap());
-            right.push(split[1].parse::<usize>().unwrap());
-        });
+        let (left, right) = parse(EXAMPLE).unwrap();
 
         let p2 = p2(&left, &right);
 
         assert_eq!(p2, 31);
     }
+
+    #[test]
+    fn bad_input() {
+        let err = parse("3   4\n4   x").unwrap_err();
+        assert_eq!(err.to_string(), "line 2");
+    }
 }
@@ -1,4 +1,5 @@
-use hashbrown::HashMap;
+use aoc_helpers::{Grid, SparseGrid};
+use std::collections::HashMap;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 struct Point {
@@ -30,8 +31,9 @@ impl Point {
     }
 }
 
+#[aoc::part1(08)]
 fn solve((grid_points, max): &(HashMap<u8, Vec<Point>>, Point)) -> usize {
-    let mut grid = vec![vec![false; max.y as usize]; max.x as usize];
+    let mut antinodes = SparseGrid::new();
 
     for points in grid_points.values() {
         for &a in points {
@@ -43,20 +45,21 @@ fn solve((grid_points, max): &(HashMap<u8, Vec<Point>>, Point)) -> usize {
                 let p2 = b.sub(diff);
 
                 if p1.in_bounds(*max) {
-                    grid[p1.x as usize][p1.y as usize] = true;
+                    antinodes.insert((p1.y, p1.x), ());
                 }
                 if p2.in_bounds(*max) {
-                    grid[p2.x as usize][p2.y as usize] = true;
+                    antinodes.insert((p2.y, p2.x), ());
                 }
             }
         }
     }
 
-    grid.iter().flatten().filter(|&&v| v).count()
+    antinodes.len()
 }
 
+#[aoc::part2(08)]
 fn solve2((grid_points, max): &(HashMap<u8, Vec<Point>>, Point)) -> usize {
-    let mut grid = vec![vec![false; max.y as usize]; max.x as usize];
+    let mut antinodes = SparseGrid::new();
 
     for points in grid_points.values() {
         for &a in points {
@@ -65,14 +68,14 @@ fn solve2((grid_points, max): &(HashMap<u8, Vec<Point>>, Point)) -> usize {
 
                 let mut tmp = a;
                 while tmp.in_bounds(*max) {
-                    grid[tmp.x as usize][tmp.y as usize] = true;
+                    antinodes.insert((tmp.y, tmp.x), ());
                     tmp.x += diff.x;
                     tmp.y += diff.y;
                 }
 
                 let mut tmp = b;
                 while tmp.in_bounds(*max) {
-                    grid[tmp.x as usize][tmp.y as usize] = true;
+                    antinodes.insert((tmp.y, tmp.x), ());
                     tmp.x -= diff.x;
                     tmp.y -= diff.y;
                 }
@@ -80,38 +83,29 @@ fn solve2((grid_points, max): &(HashMap<u8, Vec<Point>>, Point)) -> usize {
         }
     }
 
-    grid.iter().flatten().filter(|&&v| v).count()
+    antinodes.len()
 }
 
+#[aoc::parse(08)]
 fn parse(input: &str) -> (HashMap<u8, Vec<Point>>, Point) {
-    let mut antennas = HashMap::new();
-    let mut max_x = 0;
-    let mut max_y = 0;
-
-    for (y, line) in input.lines().enumerate() {
-        max_y = y as isize + 1;
-        for (x, ch) in line.bytes().enumerate() {
-            max_x = max_x.max(x as isize + 1);
-            if ch != b'.' {
-                antennas
-                    .entry(ch)
-                    .and_modify(|v: &mut Vec<Point>| v.push(Point::new(x as isize, y as isize)))
-                    .or_insert(vec![Point::new(x as isize, y as isize)]);
-            }
-        }
-    }
-
-    (antennas, Point::new(max_x, max_y))
-}
-
-#[aoc::main(08)]
-fn main(input: &str) -> (usize, usize) {
-    let data = parse(input);
-
-    let p1 = solve(&data);
-    let p2 = solve2(&data);
-
-    (p1, p2)
+    let grid = Grid::<u8>::parse(input).unwrap();
+
+    let antennas = grid
+        .group_positions(|&ch| ch != b'.')
+        .into_iter()
+        .map(|(ch, positions)| {
+            let points = positions
+                .into_iter()
+                .map(|(y, x)| Point::new(x as isize, y as isize))
+                .collect();
+            (ch, points)
+        })
+        .collect();
+
+    (
+        antennas,
+        Point::new(grid.width() as isize, grid.height() as isize),
+    )
 }
 
 #[cfg(test)]
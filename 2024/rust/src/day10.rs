@@ -1,22 +1,15 @@
+use aoc_helpers::Grid;
 use aoc_runner_derive::{aoc, aoc_generator};
 use hashbrown::HashSet;
 
-const DIRECTIONS: [(i32, i32); 4] = [
-    (0, 1),  // right
-    (1, 0),  // down
-    (0, -1), // left
-    (-1, 0), // up
-];
-
 #[derive(Debug)]
 struct TrailNode {
-    r: usize,
-    c: usize,
+    pos: (usize, usize),
     value: u32,
 }
 
 fn search(
-    grid: &[Vec<u32>],
+    grid: &Grid<u32>,
     node: &TrailNode,
     depth: usize,
     score: &mut usize,
@@ -27,44 +20,28 @@ fn search(
         match p2 {
             true => *score += 1,
             false => {
-                if !nines.contains(&(node.r, node.c)) {
+                if !nines.contains(&node.pos) {
                     *score += 1;
-                    nines.insert((node.r, node.c));
+                    nines.insert(node.pos);
                 }
             }
         }
         return true;
     }
-    let nr = grid.len();
-    let nc = grid[0].len();
-
-    let r = node.r;
-    let c = node.c;
-
-    for direction in DIRECTIONS.iter() {
-        let (dx, dy) = direction;
-        let nx = r as i32 + dx;
-        let ny = c as i32 + dy;
 
-        if nx >= 0 && nx < nr as i32 && ny >= 0 && ny < nc as i32 {
-            let nx = nx as usize;
-            let ny = ny as usize;
-
-            if grid[nx][ny] == node.value + 1 {
-                let nx_val = grid[nx][ny];
-                search(
-                    grid,
-                    &TrailNode {
-                        r: nx,
-                        c: ny,
-                        value: nx_val,
-                    },
-                    depth + 1,
-                    score,
-                    nines,
-                    p2,
-                );
-            }
+    for next in grid.neighbors4(node.pos) {
+        if grid[next] == node.value + 1 {
+            search(
+                grid,
+                &TrailNode {
+                    pos: next,
+                    value: grid[next],
+                },
+                depth + 1,
+                score,
+                nines,
+                p2,
+            );
         }
     }
 
@@ -72,43 +49,32 @@ fn search(
 }
 
 #[aoc_generator(day10)]
-fn parse(input: &str) -> (Vec<TrailNode>, Vec<Vec<u32>>) {
-    let mut trailheads = vec![];
-    let grid = input
-        .lines()
+fn parse(input: &str) -> (Vec<TrailNode>, Grid<u32>) {
+    let grid = Grid::parse(input).unwrap();
+    let trailheads = grid
         .enumerate()
-        .map(|(r, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(c, ch)| {
-                    let digit = ch.to_digit(10).unwrap();
-                    if digit == 0 {
-                        trailheads.push(TrailNode { r, c, value: 0 });
-                    }
-                    digit
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+        .filter(|&(_, &value)| value == 0)
+        .map(|(pos, &value)| TrailNode { pos, value })
+        .collect();
 
     (trailheads, grid)
 }
 
 #[aoc(day10, part1)]
-fn part1((trailheads, grid): &(Vec<TrailNode>, Vec<Vec<u32>>)) -> usize {
+fn part1((trailheads, grid): &(Vec<TrailNode>, Grid<u32>)) -> usize {
     let mut score = 0;
     for node in trailheads.iter() {
-        search(&grid, &node, 0, &mut score, &mut HashSet::new(), false);
+        search(grid, node, 0, &mut score, &mut HashSet::new(), false);
     }
 
     score
 }
 
 #[aoc(day10, part2)]
-fn part2((trailheads, grid): &(Vec<TrailNode>, Vec<Vec<u32>>)) -> usize {
+fn part2((trailheads, grid): &(Vec<TrailNode>, Grid<u32>)) -> usize {
     let mut score = 0;
     for node in trailheads.iter() {
-        search(&grid, &node, 0, &mut score, &mut HashSet::new(), true);
+        search(grid, node, 0, &mut score, &mut HashSet::new(), true);
     }
     score
 }
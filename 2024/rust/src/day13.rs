@@ -1,3 +1,4 @@
+use aoc_helpers::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 const COST_A: u8 = 3;
@@ -14,75 +15,45 @@ struct Machine {
     prize: Cord,
 }
 
-fn parse_button(line: &str) -> Cord {
-    let button: Vec<String> = line.split_whitespace().map(|s| s.to_string()).collect();
-    let x_slice = button[2][2..button[2].len() - 1].to_string();
-    let y_slice = button[3][2..].to_string();
-
-    Cord {
-        x: x_slice.parse().unwrap(),
-        y: y_slice.parse().unwrap(),
-    }
-}
-
-fn parse_prize(line: &str) -> Cord {
-    let button: Vec<String> = line.split_whitespace().map(|s| s.to_string()).collect();
-    let x_slice = button[1][2..button[1].len() - 1].to_string();
-    let y_slice = button[2][2..].to_string();
-
-    Cord {
-        x: x_slice.parse().unwrap(),
-        y: y_slice.parse().unwrap(),
-    }
+/// Parses `<label>X+94, Y+34`, or `X=` and `Y=` if `sign` is `'='`.
+fn parse_cord<'a>(
+    source: Source<'a>,
+    line: &'a str,
+    label: &str,
+    sign: char,
+) -> Result<Cord, ParseError> {
+    let (x, y) = source.split_once(source.strip_prefix(line, label)?, ", ")?;
+    Ok(Cord {
+        x: source.parse(source.strip_prefix(x, &format!("X{sign}"))?)?,
+        y: source.parse(source.strip_prefix(y, &format!("Y{sign}"))?)?,
+    })
 }
 
 #[aoc_generator(day13, part1)]
-fn parse(input: &str) -> Vec<Machine> {
-    let mut blocks = input.split("\n\n");
-
-    let mut machines = vec![];
-
-    while let Some(block) = blocks.next() {
-        let mut lines = block.lines();
-        let a_button = parse_button(lines.next().unwrap());
-        let b_button = parse_button(lines.next().unwrap());
-        let prize = parse_prize(lines.next().unwrap());
-
-        let machine = Machine {
-            a: a_button,
-            b: b_button,
-            prize: prize,
-        };
-        machines.push(machine);
-    }
-
-    machines
+fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    let source = Source::new(input);
+    input
+        .split("\n\n")
+        .map(|block| {
+            let (a, rest) = source.split_once(block, "\n")?;
+            let (b, prize) = source.split_once(rest, "\n")?;
+            Ok(Machine {
+                a: parse_cord(source, a, "Button A: ", '+')?,
+                b: parse_cord(source, b, "Button B: ", '+')?,
+                prize: parse_cord(source, prize.trim_end(), "Prize: ", '=')?,
+            })
+        })
+        .collect()
 }
 
 #[aoc_generator(day13, part2)]
-fn parse2(input: &str) -> Vec<Machine> {
-    let mut blocks = input.split("\n\n");
-
-    let mut machines = vec![];
-    while let Some(block) = blocks.next() {
-        let mut lines = block.lines();
-
-        let a_button = parse_button(lines.next().unwrap());
-        let b_button = parse_button(lines.next().unwrap());
-        let prize = parse_prize(lines.next().unwrap());
-
-        let machine = Machine {
-            a: a_button,
-            b: b_button,
-            prize: Cord {
-                x: prize.x + 10000000000000,
-                y: prize.y + 10000000000000,
-            },
-        };
-        machines.push(machine);
+fn parse2(input: &str) -> Result<Vec<Machine>, ParseError> {
+    let mut machines = parse(input)?;
+    for machine in &mut machines {
+        machine.prize.x += 10000000000000;
+        machine.prize.y += 10000000000000;
     }
-
-    machines
+    Ok(machines)
 }
 
 #[aoc(day13, part1, bruteforce)]
@@ -198,11 +169,11 @@ Prize: X=18641, Y=10279"#;
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse(EXAMPLE)), 480);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 480);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse2(EXAMPLE)), 875318608908);
+        assert_eq!(part2(&parse2(EXAMPLE).unwrap()), 875318608908);
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use aoc_helpers::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 const WIDTH: usize = 101;
@@ -12,24 +13,20 @@ struct Robot {
 }
 
 #[aoc_generator(day14)]
-fn parse(input: &str) -> Vec<Robot> {
+fn parse(input: &str) -> Result<Vec<Robot>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|line| {
-            let (p_part, v_part) = line.split_once(' ').unwrap();
+            let (p_part, v_part) = source.split_once(line, " ")?;
 
-            let p = p_part[2..].split_once(',').unwrap();
-            let v = v_part[2..].split_once(',').unwrap();
+            let p = source.split_once(source.strip_prefix(p_part, "p=")?, ",")?;
+            let v = source.split_once(source.strip_prefix(v_part, "v=")?, ",")?;
 
-            let p_x = p.0.parse::<usize>().unwrap();
-            let p_y = p.1.parse::<usize>().unwrap();
-            let v_x = v.0.parse::<i32>().unwrap();
-            let v_y = v.1.parse::<i32>().unwrap();
-
-            Robot {
-                pos: (p_x, p_y),
-                vel: (v_x, v_y),
-            }
+            Ok(Robot {
+                pos: (source.parse(p.0)?, source.parse(p.1)?),
+                vel: (source.parse(v.0)?, source.parse(v.1)?),
+            })
         })
         .collect()
 }
@@ -108,6 +105,6 @@ p=9,5 v=-3,-3"#;
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse(EXAMPLE)), 21);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 21);
     }
 }
@@ -1,122 +1,63 @@
 use itertools::Itertools;
 use std::collections::{HashSet, VecDeque};
 
+use aoc_helpers::{Direction, Grid};
 use aoc_runner_derive::{aoc, aoc_generator};
 
-const DIRECTIONS: [(i32, i32); 4] = [
-    (-1, 0), // up
-    (1, 0),  // down
-    (0, -1), // left
-    (0, 1),  // right
-];
-const UP: (i32, i32) = DIRECTIONS[0];
-const DOWN: (i32, i32) = DIRECTIONS[1];
-const LEFT: (i32, i32) = DIRECTIONS[2];
-const RIGHT: (i32, i32) = DIRECTIONS[3];
+type ParsedInput = (Grid<u8>, Vec<Direction>, (usize, usize));
 
-type ParsedInput = (Vec<Vec<u8>>, Vec<(i32, i32)>, (usize, usize));
+fn parse_warehouse(map: &str, moves: &str) -> ParsedInput {
+    let grid = Grid::<u8>::parse(map).unwrap();
 
-#[aoc_generator(day15, part1)]
-fn parse(input: &str) -> ParsedInput {
-    let (top, bottom) = input.split_once("\n\n").unwrap();
-
-    let grid = top
-        .lines()
-        .map(|line| line.bytes().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let instructions = bottom
+    let instructions = moves
         .lines()
-        .flat_map(|line| {
-            line.chars().map(|c| match c {
-                '^' => UP,
-                'v' => DOWN,
-                '<' => LEFT,
-                '>' => RIGHT,
-                _ => unreachable!(),
-            })
-        })
+        .flat_map(|line| line.chars().map(|c| Direction::try_from(c).unwrap()))
         .collect::<Vec<_>>();
 
-    let start = grid
-        .iter()
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .enumerate()
-                .find_map(|(c, &cell)| if cell == b'@' { Some((r, c)) } else { None })
-        })
-        .unwrap();
+    let start = grid.find_unique(&b'@').unwrap();
 
     (grid, instructions, start)
 }
 
+#[aoc_generator(day15, part1)]
+fn parse(input: &str) -> ParsedInput {
+    let (top, bottom) = input.split_once("\n\n").unwrap();
+    parse_warehouse(top, bottom)
+}
+
 fn solve((grid, moves, start): &ParsedInput) -> usize {
     let mut grid = grid.clone();
     let mut pos = *start;
 
-    for (dr, dc) in moves {
-        if let Some(new_pos) = r#move((*dr, *dc), &mut grid, (pos.0, pos.1)) {
+    for &dir in moves {
+        if let Some(new_pos) = r#move(dir, &mut grid, (pos.0, pos.1)) {
             pos = new_pos;
         }
     }
 
-    let mut total = 0;
-    for r in 0..grid.len() {
-        for c in 0..grid[0].len() {
-            if grid[r][c] == b'[' || grid[r][c] == b'O' {
-                total += 100 * r + c;
-            }
-        }
-    }
-
-    total
+    grid.enumerate()
+        .filter(|&(_, &cell)| cell == b'[' || cell == b'O')
+        .map(|((r, c), _)| 100 * r + c)
+        .sum()
 }
 
 #[aoc_generator(day15, part2)]
 fn parse2(input: &str) -> ParsedInput {
     let (top, bottom) = input.split_once("\n\n").unwrap();
 
-    let grid = top
-        .lines()
-        .map(|line| {
-            line.bytes()
-                .flat_map(|c| match c {
-                    b'#' => b"##",
-                    b'O' => b"[]",
-                    b'.' => b"..",
-                    b'@' => b"@.",
-                    _ => unreachable!(),
-                })
-                .copied()
-                .collect::<Vec<u8>>()
+    let widened = top
+        .chars()
+        .map(|c| match c {
+            '#' => "##",
+            'O' => "[]",
+            '.' => "..",
+            '@' => "@.",
+            '\n' => "\n",
+            _ => unreachable!(),
         })
-        .collect::<Vec<_>>();
+        .collect::<String>();
 
-    let instructions = bottom
-        .lines()
-        .flat_map(|line| {
-            line.chars().map(|c| match c {
-                '^' => UP,
-                'v' => DOWN,
-                '<' => LEFT,
-                '>' => RIGHT,
-                _ => unreachable!(),
-            })
-        })
-        .collect::<Vec<_>>();
-
-    let start = grid
-        .iter()
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .enumerate()
-                .find_map(|(c, &cell)| if cell == b'@' { Some((r, c)) } else { None })
-        })
-        .unwrap();
-
-    (grid, instructions, start)
+    parse_warehouse(&widened, bottom)
 }
 
 #[aoc(day15, part1)]
@@ -129,12 +70,9 @@ fn part2(input: &ParsedInput) -> usize {
     solve(input)
 }
 
-fn r#move(
-    dir: (i32, i32),
-    griddy: &mut Vec<Vec<u8>>,
-    (r, c): (usize, usize),
-) -> Option<(usize, usize)> {
-    let (rr, cc) = ((r as i32 + dir.0) as usize, (c as i32 + dir.1) as usize);
+fn r#move(dir: Direction, griddy: &mut Grid<u8>, (r, c): (usize, usize)) -> Option<(usize, usize)> {
+    let dir = dir.delta();
+    let (rr, cc) = ((r as isize + dir.0) as usize, (c as isize + dir.1) as usize);
     let mut q = VecDeque::from([(r, c)]);
     let mut seen = HashSet::new();
 
@@ -142,8 +80,11 @@ fn r#move(
         if !seen.insert((rr, cc)) {
             continue;
         }
-        let (r2, c2) = ((rr as i32 + dir.0) as usize, (cc as i32 + dir.1) as usize);
-        match griddy[r2][c2] {
+        let (r2, c2) = (
+            (rr as isize + dir.0) as usize,
+            (cc as isize + dir.1) as usize,
+        );
+        match griddy[(r2, c2)] {
             b'#' => return None,
             b'O' => {
                 q.push_back((r2, c2));
@@ -161,10 +102,13 @@ fn r#move(
     }
     while !seen.is_empty() {
         for (rr, cc) in seen.iter().copied().sorted() {
-            let (r2, c2) = (rr + dir.0 as usize, cc + dir.1 as usize);
+            let (r2, c2) = (
+                (rr as isize + dir.0) as usize,
+                (cc as isize + dir.1) as usize,
+            );
             if !seen.contains(&(r2, c2)) {
-                griddy[r2][c2] = griddy[rr][cc];
-                griddy[rr][cc] = b'.';
+                griddy[(r2, c2)] = griddy[(rr, cc)];
+                griddy[(rr, cc)] = b'.';
                 seen.remove(&(rr, cc));
             }
         }
@@ -1,14 +1,18 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::GridExt;
+use aoc_helpers::{astar, dijkstra_all, Direction, Grid};
 
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::HashSet;
 
-const DIRECTIONS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Down,
+    Direction::Right,
+    Direction::Up,
+    Direction::Left,
+];
 
-const TURN_COST: i32 = 1000;
-const MOVE_COST: i32 = 1;
+const TURN_COST: usize = 1000;
+const MOVE_COST: usize = 1;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct PackedPosition(u32);
@@ -42,16 +46,9 @@ struct PackedState(u32);
 
 impl PackedState {
     #[inline(always)]
-    const fn new(pos: PackedPosition, dir: (i32, i32)) -> Self {
-        let dir_bits = match dir {
-            (0, 1) => 0,  // right
-            (1, 0) => 1,  // down
-            (0, -1) => 2, // left
-            (-1, 0) => 3, // up
-            _ => unreachable!(),
-        };
+    const fn new(pos: PackedPosition, dir: Direction) -> Self {
         // Pack position and direction into a single u32
-        PackedState(pos.0 | (dir_bits << 30))
+        PackedState(pos.0 | ((dir.to_bits() as u32) << 30))
     }
 
     #[inline(always)]
@@ -60,52 +57,24 @@ impl PackedState {
     }
 
     #[inline(always)]
-    const fn direction(self) -> (i32, i32) {
-        match (self.0 >> 30) & 0x3 {
-            0 => (0, 1),
-            1 => (1, 0),
-            2 => (0, -1),
-            3 => (-1, 0),
-            _ => unreachable!(),
-        }
-    }
-}
-
-#[derive(Eq, PartialEq)]
-struct Node {
-    state: PackedState,
-    f_score: i32,
-    g_score: i32,
-}
-
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // reverse ordering for min-heap
-        other
-            .f_score
-            .cmp(&self.f_score)
-            .then_with(|| other.g_score.cmp(&self.g_score))
+    const fn direction(self) -> Direction {
+        Direction::from_bits((self.0 >> 30) as u8)
     }
 }
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn get_neighbors(state: &PackedState, maze: &[Vec<u8>]) -> [Option<(PackedState, i32)>; 4] {
-    let mut neighbors: [Option<(PackedState, i32)>; 4] = [None; 4];
+fn get_neighbors(state: &PackedState, maze: &Grid<u8>) -> [Option<(PackedState, usize)>; 4] {
+    let mut neighbors: [Option<(PackedState, usize)>; 4] = [None; 4];
 
     for (i, &dir) in DIRECTIONS.iter().enumerate() {
-        let new_x = state.pos().x() + dir.0;
-        let new_y = state.pos().y() + dir.1;
+        let (dy, dx) = dir.delta();
+        let new_x = state.pos().x() + dx as i32;
+        let new_y = state.pos().y() + dy as i32;
 
         if new_x >= 0
-            && new_x < maze[0].len() as i32
+            && new_x < maze.width() as i32
             && new_y >= 0
-            && new_y < maze.len() as i32
-            && maze[new_y as usize][new_x as usize] != b'#'
+            && new_y < maze.height() as i32
+            && maze[(new_y as usize, new_x as usize)] != b'#'
         {
             // calculate cost based on whether we're turning
             let cost = match state.direction() {
@@ -121,184 +90,61 @@ fn get_neighbors(state: &PackedState, maze: &[Vec<u8>]) -> [Option<(PackedState,
     neighbors
 }
 
-fn astar(maze: &[Vec<u8>], start: PackedPosition, goal: PackedPosition) -> u32 {
-    let mut open_set = BinaryHeap::with_capacity(maze.len() * maze[0].len());
-
-    let start_state = PackedState::new(start, (1, 0));
-    let mut visited = HashSet::with_capacity(maze.len() * maze[0].len());
-
-    open_set.push(Node {
-        state: start_state,
-        f_score: start.manhattan_distance(&goal),
-        g_score: 0,
-    });
-
-    while let Some(current_node) = open_set.pop() {
-        let current_state = current_node.state;
-
-        if current_state.pos() == goal {
-            return current_node.g_score as u32;
-        }
-
-        let current_g = current_node.g_score;
-
-        for (neighbor_state, cost) in get_neighbors(&current_state, maze)
-            .iter()
-            .filter_map(|n| *n)
-        {
-            let tentative_g_score = current_g + cost;
-
-            if !visited.contains(&neighbor_state) {
-                visited.insert(neighbor_state);
-                open_set.push(Node {
-                    state: neighbor_state,
-                    f_score: tentative_g_score + neighbor_state.pos().manhattan_distance(&goal),
-                    g_score: tentative_g_score,
-                });
-            }
-        }
-    }
-
-    unreachable!()
+fn lowest_score(Input { maze, start, goal }: &Input) -> usize {
+    astar(
+        PackedState::new(*start, Direction::Right),
+        |state| get_neighbors(state, maze).into_iter().flatten(),
+        |state| state.pos().manhattan_distance(goal) as usize,
+        |state| state.pos() == *goal,
+    )
+    .expect("no path through the maze")
+    .cost
 }
 
 fn best_tiles(Input { maze, start, goal }: &Input) -> HashSet<PackedPosition> {
-    let (start, goal) = (*start, *goal);
-    // first pass: Find optimal path
-    let optimal_score = astar(&maze, start.clone(), goal.clone());
-
-    let start_state = PackedState::new(start, (1, 0));
-
-    // second pass: Find all tiles that are part of paths with optimal_score
-    let mut open_set = BinaryHeap::with_capacity(maze.len() * maze[0].len());
-    let mut g_scores = HashMap::new();
-    let mut best_tiles = HashSet::new();
-
-    // track multiple possible previous states for each state
-    let mut came_from: HashMap<PackedState, HashSet<PackedState>> = HashMap::new();
-
-    g_scores.insert(start_state, 0);
-    open_set.push(Node {
-        state: start_state,
-        f_score: start.manhattan_distance(&goal),
-        g_score: 0,
-    });
-
-    // find all valid paths
-    while let Some(current_node) = open_set.pop() {
-        let current_state = current_node.state;
-        let current_g = g_scores[&current_state];
-
-        // if at goal with optimal score, reconstruct all possible paths
-        if current_state.pos() == goal && current_g == optimal_score {
-            let mut stack = vec![(current_state, HashSet::new())];
-
-            while let Some((state, mut path_tiles)) = stack.pop() {
-                path_tiles.insert(state.pos());
-
-                if state.pos() == start {
-                    // found a complete path, add its tiles
-                    best_tiles.extend(path_tiles);
-                } else if let Some(prev_states) = came_from.get(&state) {
-                    // add all possible previous states to explore
-                    for prev_state in prev_states {
-                        let new_path_tiles = path_tiles.clone();
-                        stack.push((*prev_state, new_path_tiles));
-                    }
-                }
-            }
-        }
-
-        if current_g <= optimal_score {
-            for (neighbor_state, cost) in get_neighbors(&current_state, &maze)
-                .iter()
-                .filter_map(|n| *n)
-            {
-                let new_score = current_g + cost as u32;
-
-                if new_score <= optimal_score {
-                    // allow multiple paths through the same state if they have the same score
-                    if !g_scores.contains_key(&neighbor_state)
-                        || new_score == g_scores[&neighbor_state]
-                    {
-                        came_from
-                            .entry(neighbor_state)
-                            .or_insert_with(HashSet::new)
-                            .insert(current_state);
-
-                        g_scores.insert(neighbor_state, new_score);
-                        let new_f_score =
-                            new_score as i32 + neighbor_state.pos().manhattan_distance(&goal);
-                        open_set.push(Node {
-                            state: neighbor_state,
-                            f_score: new_f_score,
-                            g_score: new_score as i32,
-                        });
-                    }
-                }
-            }
-        }
-    }
-
-    best_tiles
-}
-
-#[inline(always)]
-fn reconstruct_path(
-    came_from: &HashMap<PackedState, PackedState>,
-    mut current: PackedState,
-) -> Vec<PackedPosition> {
-    let mut path = vec![current.pos()];
-
-    while let Some(prev_state) = came_from.get(&current) {
-        path.push(prev_state.pos());
-        current = *prev_state;
-    }
-
-    path.reverse();
-    path
+    let paths = dijkstra_all(
+        PackedState::new(*start, Direction::Right),
+        |state| get_neighbors(state, maze).into_iter().flatten(),
+        |state| state.pos() == *goal,
+    )
+    .expect("no path through the maze");
+
+    paths.states_on_paths().map(|state| state.pos()).collect()
 }
 
 struct Input {
-    maze: Vec<Vec<u8>>,
+    maze: Grid<u8>,
     start: PackedPosition,
     goal: PackedPosition,
 }
 
 #[aoc_generator(day16)]
 fn parse(input: &str) -> Input {
-    let maze = input
-        .lines()
-        .map(|line| line.bytes().collect())
-        .collect::<Vec<_>>();
+    let maze = Grid::<u8>::parse(input).unwrap();
 
-    let start = maze
-        .get_unique_position(b'S')
-        .map(|(y, x)| PackedPosition::new(x as i32, y as i32))
-        .unwrap();
+    let (y, x) = maze.find_unique(&b'S').unwrap();
+    let start = PackedPosition::new(x as i32, y as i32);
 
-    let goal = maze
-        .get_unique_position(b'E')
-        .map(|(y, x)| PackedPosition::new(x as i32, y as i32))
-        .unwrap();
+    let (y, x) = maze.find_unique(&b'E').unwrap();
+    let goal = PackedPosition::new(x as i32, y as i32);
 
     Input { maze, start, goal }
 }
 
 #[aoc(day16, part1)]
 fn part1(input: &Input) -> usize {
-    let score = astar(&input.maze, input.start, input.goal);
-    score as usize
+    lowest_score(input)
 }
 
 #[aoc(day16, part2)]
 fn part2(input: &Input) -> usize {
-    best_tiles(&input).len()
+    best_tiles(input).len()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_helpers::Style;
 
     const EXAMPLE: &str = r#"###############
 #.......#....E#
@@ -325,145 +171,38 @@ mod tests {
     fn part2_example() {
         assert_eq!(part2(&parse(EXAMPLE)), 45);
     }
-}
-
-mod debug {
-    use super::*;
-
-    #[allow(dead_code)]
-    fn dbg_best_tiles(
-        maze: &Vec<Vec<u8>>,
-        start: PackedPosition,
-        goal: PackedPosition,
-    ) -> Option<(Vec<PackedPosition>, i32, HashSet<PackedPosition>)> {
-        // first pass: Find optimal path
-        let (optimal_path, optimal_score) =
-            _debug_astar(maze, start.clone(), goal.clone()).unwrap();
-
-        let start_state = PackedState::new(start, (1, 0));
-
-        // second pass: Find all tiles that are part of paths with optimal_score
-        let mut open_set = BinaryHeap::new();
-        let mut g_scores = HashMap::new();
-        let mut best_tiles = HashSet::new();
-
-        // track multiple possible previous states for each state
-        let mut came_from: HashMap<PackedState, HashSet<PackedState>> = HashMap::new();
-
-        g_scores.insert(start_state, 0);
-        open_set.push(Node {
-            state: start_state,
-            f_score: start.manhattan_distance(&goal),
-            g_score: 0,
-        });
-
-        // find all valid paths
-        while let Some(current_node) = open_set.pop() {
-            let current_state = current_node.state;
-            let current_g = g_scores[&current_state];
-
-            // if at goal with optimal score, reconstruct all possible paths
-            if current_state.pos() == goal && current_g == optimal_score {
-                let mut stack = vec![(current_state, HashSet::new())];
-
-                while let Some((state, mut path_tiles)) = stack.pop() {
-                    path_tiles.insert(state.pos());
-
-                    if state.pos() == start {
-                        // found a complete path, add its tiles
-                        best_tiles.extend(path_tiles);
-                    } else if let Some(prev_states) = came_from.get(&state) {
-                        // add all possible previous states to explore
-                        for prev_state in prev_states {
-                            let new_path_tiles = path_tiles.clone();
-                            stack.push((*prev_state, new_path_tiles));
-                        }
-                    }
-                }
-            }
-
-            if current_g <= optimal_score {
-                for (neighbor_state, cost) in get_neighbors(&current_state, maze)
-                    .iter()
-                    .filter_map(|n| *n)
-                {
-                    let new_score = current_g + cost;
-
-                    if new_score <= optimal_score {
-                        // allow multiple paths through the same state if they have the same score
-                        if !g_scores.contains_key(&neighbor_state)
-                            || new_score == g_scores[&neighbor_state]
-                        {
-                            came_from
-                                .entry(neighbor_state)
-                                .or_insert_with(HashSet::new)
-                                .insert(current_state);
-
-                            g_scores.insert(neighbor_state, new_score);
-                            let new_f_score =
-                                new_score + neighbor_state.pos().manhattan_distance(&goal);
-                            open_set.push(Node {
-                                state: neighbor_state,
-                                f_score: new_f_score,
-                                g_score: new_score,
-                            });
-                        }
-                    }
-                }
-            }
-        }
 
-        Some((optimal_path, optimal_score, best_tiles))
-    }
-
-    fn _debug_astar(
-        maze: &[Vec<u8>],
-        start: PackedPosition,
-        goal: PackedPosition,
-    ) -> Option<(Vec<PackedPosition>, i32)> {
-        let mut open_set = BinaryHeap::new();
-        let mut came_from: HashMap<PackedState, PackedState> = HashMap::new();
-        let mut g_scores: HashMap<PackedState, i32> = HashMap::new();
-
-        let start_state = PackedState::new(start, (1, 0));
-
-        g_scores.insert(start_state, 0);
-        open_set.push(Node {
-            state: start_state,
-            f_score: start.manhattan_distance(&goal),
-            g_score: 0,
-        });
-
-        while let Some(current_node) = open_set.pop() {
-            let current_state = current_node.state;
-
-            if current_state.pos() == goal {
-                let total_score = g_scores[&current_state];
-                return Some((reconstruct_path(&came_from, current_state), total_score));
-            }
-
-            let current_g = g_scores[&current_state];
-
-            for (neighbor_state, cost) in get_neighbors(&current_state, maze)
-                .iter()
-                .filter_map(|n| *n)
-            {
-                let tentative_g_score = current_g + cost;
-
-                if !g_scores.contains_key(&neighbor_state)
-                    || tentative_g_score < g_scores[&neighbor_state]
-                {
-                    came_from.insert(neighbor_state, current_state);
-                    g_scores.insert(neighbor_state, tentative_g_score);
-                    open_set.push(Node {
-                        state: neighbor_state,
-                        f_score: tentative_g_score + neighbor_state.pos().manhattan_distance(&goal),
-                        g_score: tentative_g_score,
-                    });
-                }
-            }
-        }
-
-        None
+    #[test]
+    fn best_tiles_snapshot() {
+        let input = parse(EXAMPLE);
+        let tiles = best_tiles(&input)
+            .into_iter()
+            .map(|pos| (pos.y() as usize, pos.x() as usize));
+        let render = input
+            .maze
+            .render_with(|&b| b as char)
+            .overlay(tiles, Style::glyph('O'))
+            .to_string();
+
+        assert_eq!(
+            render,
+            "\
+###############
+#.......#....O#
+#.#.###.#.###O#
+#.....#.#...#O#
+#.###.#####.#O#
+#.#.#.......#O#
+#.#.#####.###O#
+#..OOOOOOOOO#O#
+###O#O#####O#O#
+#OOO#O....#O#O#
+#O#O#O###.#O#O#
+#OOOOO#...#O#O#
+#O###.#.#.#O#O#
+#O..#.....#OOO#
+###############
+"
+        );
     }
 }
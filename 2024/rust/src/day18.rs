@@ -1,29 +1,6 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
 
-use crate::DIRECTIONS4;
-
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    position: (usize, usize),
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.position.cmp(&other.position))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+use aoc_helpers::{bfs, Grid, Path, PathCache};
 
 const GRID_SIZE: usize = {
     if cfg!(test) {
@@ -33,6 +10,14 @@ const GRID_SIZE: usize = {
     }
 };
 
+const FALLEN: usize = {
+    if cfg!(test) {
+        12
+    } else {
+        1024
+    }
+};
+
 #[aoc_generator(day18)]
 fn parse(input: &str) -> Vec<(usize, usize)> {
     input
@@ -47,115 +32,47 @@ fn parse(input: &str) -> Vec<(usize, usize)> {
         .collect()
 }
 
-// Helper function to check if a path exists
-fn find_path(grid: &[Vec<u8>]) -> Option<(usize, Vec<(usize, usize)>)> {
-    let mut distances: HashMap<(usize, usize), usize> = HashMap::new();
-    let mut heap: BinaryHeap<State> = BinaryHeap::new();
-    let mut previous: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
-    let mut visited = vec![vec![false; GRID_SIZE]; GRID_SIZE];
-
-    heap.push(State {
-        cost: 0,
-        position: (0, 0),
-    });
-    distances.insert((0, 0), 0);
-
-    while let Some(State { position, cost }) = heap.pop() {
-        let (x, y) = position;
-
-        if visited[y][x] {
-            continue;
-        }
-        visited[y][x] = true;
-
-        if position == (GRID_SIZE - 1, GRID_SIZE - 1) {
-            // reconstruct path
-            let mut path = vec![];
-            let mut current = position;
-            while let Some(p) = previous.get(&current) {
-                path.push(*p);
-                current = *p;
-            }
-            path.push((0, 0));
-
-            return Some((cost, path));
-        }
-
-        for &(dx, dy) in &DIRECTIONS4 {
-            let new_x = x as i32 + dx;
-            let new_y = y as i32 + dy;
-
-            if new_x < 0 || new_x >= GRID_SIZE as i32 || new_y < 0 || new_y >= GRID_SIZE as i32 {
-                continue;
-            }
-
-            let new_pos = (new_x as usize, new_y as usize);
-
-            if grid[new_pos.1][new_pos.0] == b'#' || visited[new_pos.1][new_pos.0] {
-                continue;
-            }
+/// Shortest path from the top-left to the bottom-right corner, if one exists.
+fn find_path(grid: &Grid<bool>) -> Option<Path<(usize, usize)>> {
+    bfs(
+        (0, 0),
+        |&pos| grid.neighbors4(pos).filter(|&n| !grid[n]),
+        |&pos| pos == (GRID_SIZE - 1, GRID_SIZE - 1),
+    )
+}
 
-            heap.push(State {
-                cost: distances[&position] + 1,
-                position: new_pos,
-            });
-            distances.insert(new_pos, distances[&position] + 1);
-            previous.insert(new_pos, position);
-        }
+/// A grid with the first `count` bytes marked as corrupted.
+fn corrupted(input: &[(usize, usize)], count: usize) -> Grid<bool> {
+    let mut grid = Grid::new(GRID_SIZE, GRID_SIZE, false);
+    for &(x, y) in input.iter().take(count) {
+        grid[(y, x)] = true;
     }
-
-    None
+    grid
 }
 
 #[aoc(day18, part1)]
 fn part1(input: &[(usize, usize)]) -> usize {
-    // let grid_size = *grid_size;
-    let mut grid = vec![vec![b'.'; GRID_SIZE]; GRID_SIZE];
-
-    // tests
-    for &(x, y) in input.iter().take(match GRID_SIZE {
-        7 => 12,
-        71 => 1024,
-        _ => unreachable!("Bad grid size"),
-    }) {
-        grid[y][x] = b'#';
-    }
-
-    let (cost, _) = find_path(&grid).expect("No path found");
-
-    cost
+    find_path(&corrupted(input, FALLEN))
+        .expect("No path found")
+        .cost
 }
 
 #[aoc(day18, part2)]
 fn part2(input: &[(usize, usize)]) -> String {
-    let mut grid = vec![vec![b'.'; GRID_SIZE]; GRID_SIZE];
-
     // based off pt 1, we know that a valid input will have have a path after atleast this many obstacles so we can skip them
-    for &(x, y) in input.iter().take(match GRID_SIZE {
-        7 => 12,
-        71 => 1024,
-        _ => unreachable!("Bad grid size"),
-    }) {
-        grid[y][x] = b'#';
-    }
-
-    let mut last_path: Option<Vec<(usize, usize)>> = None;
+    let mut grid = corrupted(input, FALLEN);
+    let mut cache = PathCache::new();
 
-    for &(x, y) in input.iter() {
-        grid[y][x] = b'#';
+    for &(x, y) in &input[FALLEN..] {
+        grid[(y, x)] = true;
 
-        // if the last found path doesn't contain the new point, skip it
-        if let Some(ref path) = last_path {
-            if !path.iter().any(|&(px, py)| px == x && py == y) {
-                continue;
-            }
-        }
-
-        let Some((_, path)) = find_path(&grid) else {
+        // only search again if the new byte lands on the last path found
+        if cache
+            .get_or_search(|&pos| pos == (y, x), || find_path(&grid))
+            .is_none()
+        {
             return format!("{},{}", x, y);
-        };
-
-        last_path = Some(path);
+        }
     }
 
     unreachable!("No solution found");
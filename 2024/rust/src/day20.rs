@@ -1,4 +1,4 @@
-use crate::GridExt;
+use aoc_helpers::Grid;
 use aoc_runner_derive::{aoc, aoc_generator};
 
 #[derive(Debug, Clone)]
@@ -6,128 +6,88 @@ struct Cheat {
     turns_saved: usize,
 }
 
-type Parsed = (Vec<Vec<u8>>, (usize, usize), (usize, usize));
+type Parsed = (Grid<u8>, (usize, usize), (usize, usize));
 
 #[aoc_generator(day20)]
 fn parse(input: &str) -> Parsed {
-    let grid: Vec<Vec<u8>> = input.lines().map(|l| l.bytes().collect()).collect();
-    let start = grid.get_unique_position(b'S').unwrap();
-    let end = grid.get_unique_position(b'E').unwrap();
+    let grid = Grid::<u8>::parse(input).unwrap();
+    let start = grid.find_unique(&b'S').unwrap();
+    let end = grid.find_unique(&b'E').unwrap();
     (grid, start, end)
 }
 
-fn solve(
-    start: (usize, usize),
-    end: (usize, usize),
-    grid: &[Vec<u8>],
-    max_distance: usize,
-) -> Vec<Cheat> {
-    let mut cheats = Vec::new();
-
-    let mut start_distances = vec![vec![usize::MAX; grid[0].len()]; grid.len()];
-    let mut end_distances = vec![vec![usize::MAX; grid[0].len()]; grid.len()];
+fn distances_from(grid: &Grid<u8>, source: (usize, usize)) -> Grid<usize> {
+    let mut distances = Grid::new(grid.width(), grid.height(), usize::MAX);
 
-    // calculate distances from start
     let mut queue = std::collections::VecDeque::new();
-    queue.push_back((start, 0));
+    queue.push_back((source, 0));
     while let Some((pos, dist)) = queue.pop_front() {
-        if start_distances[pos.0][pos.1] != usize::MAX {
+        if distances[pos] != usize::MAX {
             continue;
         }
 
-        start_distances[pos.0][pos.1] = dist;
+        distances[pos] = dist;
 
-        for (dy, dx) in &[(0, 1), (0, -1), (1, 0), (-1, 0)] {
-            let new_pos = (pos.0 as isize + dy, pos.1 as isize + dx);
-            if new_pos.0 < 0 || new_pos.1 < 0 {
-                continue;
-            }
-            let new_pos = (new_pos.0 as usize, new_pos.1 as usize);
-            if new_pos.0 >= grid.len() || new_pos.1 >= grid[0].len() {
-                continue;
-            }
-            if grid[new_pos.0][new_pos.1] == b'#' {
-                continue;
+        for delta in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+            if let Some(new_pos) = grid.offset(pos, delta) {
+                if grid[new_pos] != b'#' {
+                    queue.push_back((new_pos, dist + 1));
+                }
             }
-            queue.push_back((new_pos, dist + 1));
         }
     }
 
-    // calculate distances from end
-    let mut queue = std::collections::VecDeque::new();
-    queue.push_back((end, 0));
-    while let Some((pos, dist)) = queue.pop_front() {
-        if end_distances[pos.0][pos.1] != usize::MAX {
+    distances
+}
+
+fn solve(
+    start: (usize, usize),
+    end: (usize, usize),
+    grid: &Grid<u8>,
+    max_distance: usize,
+) -> Vec<Cheat> {
+    let mut cheats = Vec::new();
+
+    let start_distances = distances_from(grid, start);
+    let end_distances = distances_from(grid, end);
+
+    let original_distance = end_distances[start];
+
+    // look for cheats
+    for pos in grid.positions() {
+        if grid[pos] == b'#' {
+            continue;
+        }
+        if start_distances[pos] == usize::MAX {
             continue;
         }
 
-        end_distances[pos.0][pos.1] = dist;
+        // check all points within manhattan distance
+        for di in -(max_distance as isize)..=max_distance as isize {
+            for dj in -(max_distance as isize)..=max_distance as isize {
+                let manhattan = di.abs() + dj.abs();
+                if manhattan as usize > max_distance {
+                    continue;
+                }
 
-        for (dy, dx) in &[(0, 1), (0, -1), (1, 0), (-1, 0)] {
-            let new_pos = (pos.0 as isize + dy, pos.1 as isize + dx);
-            if new_pos.0 < 0 || new_pos.1 < 0 {
-                continue;
-            }
-            let new_pos = (new_pos.0 as usize, new_pos.1 as usize);
-            if new_pos.0 >= grid.len() || new_pos.1 >= grid[0].len() {
-                continue;
-            }
-            if grid[new_pos.0][new_pos.1] == b'#' {
-                continue;
-            }
-            queue.push_back((new_pos, dist + 1));
-        }
-    }
+                let Some(end_pos) = grid.offset(pos, (di, dj)) else {
+                    continue;
+                };
 
-    let original_distance = end_distances[start.0][start.1];
+                if grid[end_pos] == b'#' {
+                    continue;
+                }
 
-    // look for cheats
-    for i in 0..grid.len() {
-        for j in 0..grid[0].len() {
-            // let mid_point = (i, j);
-            if grid[i][j] == b'#' {
-                continue;
-            }
-            if start_distances[i][j] == usize::MAX {
-                continue;
-            }
+                if end_distances[end_pos] == usize::MAX {
+                    continue;
+                }
+
+                let new_distance =
+                    start_distances[pos] + end_distances[end_pos] + manhattan as usize;
+                let turns_saved = original_distance.saturating_sub(new_distance);
 
-            // check all points within manhattan distance
-            for di in -(max_distance as isize)..=max_distance as isize {
-                for dj in -(max_distance as isize)..=max_distance as isize {
-                    let manhattan = di.abs() + dj.abs();
-                    if manhattan as usize > max_distance {
-                        continue;
-                    }
-
-                    let end_i = i as isize + di;
-                    let end_j = j as isize + dj;
-
-                    if end_i < 0 || end_j < 0 {
-                        continue;
-                    }
-                    let end_i = end_i as usize;
-                    let end_j = end_j as usize;
-
-                    if end_i >= grid.len() || end_j >= grid[0].len() {
-                        continue;
-                    }
-
-                    if grid[end_i][end_j] == b'#' {
-                        continue;
-                    }
-
-                    if end_distances[end_i][end_j] == usize::MAX {
-                        continue;
-                    }
-
-                    let new_distance =
-                        start_distances[i][j] + end_distances[end_i][end_j] + manhattan as usize;
-                    let turns_saved = original_distance.saturating_sub(new_distance);
-
-                    if turns_saved > 0 {
-                        cheats.push(Cheat { turns_saved });
-                    }
+                if turns_saved > 0 {
+                    cheats.push(Cheat { turns_saved });
                 }
             }
         }
@@ -1,5 +1,6 @@
 use std::{collections::HashMap, mem, rc::Rc};
 
+use aoc_helpers::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 type Input = (Wires, Vec<Instance>);
@@ -14,34 +15,33 @@ struct Instance {
 }
 
 #[aoc_generator(day24)]
-fn parse(input: &str) -> Input {
-    let (a, b) = input.split_once("\n\n").unwrap();
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+    let (a, b) = source.split_once(input, "\n\n")?;
 
     let mut wires = HashMap::new();
     let mut instances = vec![];
 
     for line in a.lines() {
-        let (l, r) = line.split_once(": ").unwrap();
+        let (l, r) = source.split_once(line, ": ")?;
 
-        wires.insert(l.into(), r.parse::<u8>().unwrap());
+        wires.insert(l.into(), source.parse::<u8>(r)?);
     }
 
     for line in b.lines() {
-        let (l, out) = line.split_once(" -> ").unwrap();
-        let mut w_split = l.split_whitespace();
-        let lhs = w_split.next().unwrap().into();
-        let op = w_split.next().unwrap().to_string();
-        let rhs = w_split.next().unwrap().into();
+        let (l, out) = source.split_once(line, " -> ")?;
+        let (lhs, rest) = source.split_once(l, " ")?;
+        let (op, rhs) = source.split_once(rest, " ")?;
 
         instances.push(Instance {
-            lhs,
-            rhs,
-            op,
+            lhs: lhs.into(),
+            rhs: rhs.into(),
+            op: op.to_string(),
             out: out.into(),
         });
     }
 
-    (wires, instances)
+    Ok((wires, instances))
 }
 
 #[inline(always)]
@@ -291,14 +291,15 @@ tnw OR pbm -> gnj"#;
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse(EXAMPLE)), 2024);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 2024);
     }
 
     #[test]
+    #[cfg(feature = "embed-inputs")]
     fn part2_example() {
         // example for p2 wasn't that good
         assert_eq!(
-            part2(&parse(include_str!("../input/2024/day24.txt"))),
+            part2(&parse(include_str!("../input/2024/day24.txt")).unwrap()),
             "cqm,mps,vcv,vjv,vwp,z13,z19,z25"
         );
     }
@@ -19,46 +19,6 @@ mod day9;
 extern crate aoc_runner_derive;
 extern crate aoc_runner;
 
-pub use aoc_macros::main;
-
-/// Directions for 4-way movement (right, down, left, up)
-pub const DIRECTIONS4: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-
-pub trait GridExt<S> {
-    /// Returns the first unique position of the given identifier in the grid.
-    ///
-    /// (row, column)
-    fn get_unique_position(&self, identifier: S) -> Option<(usize, usize)>;
-}
-
-impl<S: PartialEq> GridExt<S> for Vec<Vec<S>> {
-    fn get_unique_position(&self, identifier: S) -> Option<(usize, usize)> {
-        self.iter().enumerate().find_map(|(r, row)| {
-            row.iter().enumerate().find_map(|(c, cell)| {
-                if *cell == identifier {
-                    Some((r, c))
-                } else {
-                    None
-                }
-            })
-        })
-    }
-}
-
-impl<S: PartialEq> GridExt<S> for [Vec<S>] {
-    fn get_unique_position(&self, identifier: S) -> Option<(usize, usize)> {
-        self.iter().enumerate().find_map(|(r, row)| {
-            row.iter().enumerate().find_map(|(c, cell)| {
-                if *cell == identifier {
-                    Some((r, c))
-                } else {
-                    None
-                }
-            })
-        })
-    }
-}
-
-pub use GridExt as _;
+pub use aoc_macros::{main, parse, part1, part2};
 
 aoc_lib! { year = 2024 }
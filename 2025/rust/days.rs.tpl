@@ -22,11 +22,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse(&EXAMPLE)), 0);
+        assert_eq!(part1(&parse(&EXAMPLE)), <PART1>);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse(&EXAMPLE)), 0);
+        assert_eq!(part2(&parse(&EXAMPLE)), <PART2>);
     }
 }
@@ -0,0 +1 @@
+aoc_macros::runner_main!(lib = aoc);
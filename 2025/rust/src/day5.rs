@@ -1,3 +1,4 @@
+use aoc_helpers::{ParseError, Source};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 #[derive(Debug)]
@@ -36,19 +37,23 @@ impl Inventory {
 }
 
 #[aoc_generator(day5)]
-fn parse(input: &str) -> Inventory {
-    let (ranges, ids) = input.split_once("\n\n").unwrap();
+fn parse(input: &str) -> Result<Inventory, ParseError> {
+    let source = Source::new(input);
+    let (ranges, ids) = source.split_once(input, "\n\n")?;
     let ranges = ranges
         .lines()
         .map(|line| {
-            let (start, end) = line.split_once('-').unwrap();
-            (start.parse().unwrap(), end.parse().unwrap())
+            let (start, end) = source.split_once(line, "-")?;
+            Ok((source.parse(start)?, source.parse(end)?))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    let ids = ids.lines().map(|line| line.parse().unwrap()).collect();
+    let ids = ids
+        .lines()
+        .map(|line| source.parse(line))
+        .collect::<Result<_, _>>()?;
 
-    Inventory { ranges, ids }
+    Ok(Inventory { ranges, ids })
 }
 
 #[aoc(day5, part1)]
@@ -116,11 +121,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse(EXAMPLE)), 3);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 3);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse(EXAMPLE)), 14);
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 14);
     }
 }